@@ -0,0 +1,30 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc01::{Calibration, DigitMode};
+use aoc01::generator::generate;
+use aoc01::vocabulary::Vocabulary;
+
+fn phases(c: &mut Criterion) {
+    // Day 1 reads its lines as they come, building the scanner is all there is to parse.
+    let mut document = Vec::new();
+    generate(&mut document, 0x2023_1201, 100_000).unwrap();
+
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("aoc01a digits", |b| b.iter(|| Calibration::new(DigitMode::Numeric)));
+    group.bench_function("aoc01b english words", |b| b.iter(|| {
+        Calibration::new(DigitMode::NumericAndWords(black_box(Vocabulary::english())))
+    }));
+    group.finish();
+
+    let mut group = c.benchmark_group("solve");
+    let numeric = Calibration::new(DigitMode::Numeric);
+    let words = Calibration::new(DigitMode::NumericAndWords(Vocabulary::english()));
+    group.bench_function("aoc01a 100000 lines", |b| b.iter(|| numeric.summary(black_box(document.as_slice())).unwrap()));
+    group.bench_function("aoc01b 100000 lines", |b| b.iter(|| words.summary(black_box(document.as_slice())).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, phases);
+criterion_main!(benches);
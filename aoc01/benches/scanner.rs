@@ -0,0 +1,108 @@
+use std::cmp::{max, min};
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use aoc01::{Calibration, DigitMode};
+use aoc01::generator::generate;
+use aoc01::vocabulary::Vocabulary;
+
+static NUMBERS: [(&str, &str); 9] = [
+    ("1", "one"), ("2", "two"), ("3", "three"), ("4", "four"), ("5", "five"),
+    ("6", "six"), ("7", "seven"), ("8", "eight"), ("9", "nine"),
+];
+
+// The previous approach: one find (rfind) per number and textual representation
+fn naive_first_digit(line: &str) -> char {
+    let mut smallest_pos = usize::MAX;
+    let mut smallest_number = "";
+
+    for (number, number_text) in NUMBERS {
+        let pos = min(
+            line.find(number).unwrap_or(usize::MAX),
+            line.find(number_text).unwrap_or(usize::MAX),
+        );
+
+        if pos < smallest_pos {
+            smallest_pos = pos;
+            smallest_number = number;
+        }
+    }
+
+    smallest_number.parse().unwrap()
+}
+
+fn naive_last_digit(line: &str) -> char {
+    let mut greatest_pos = -1;
+    let mut greatest_number = "";
+
+    for (number, number_text) in NUMBERS {
+        let pos_number = line.rfind(number);
+        let pos_number_text = line.rfind(number_text);
+
+        if pos_number.is_some() | pos_number_text.is_some() {
+            let pos = max(pos_number, pos_number_text).unwrap() as i32;
+
+            if pos > greatest_pos {
+                greatest_pos = pos;
+                greatest_number = number;
+            }
+        }
+    }
+
+    greatest_number.to_string().chars().next().unwrap()
+}
+
+fn scanner(c: &mut Criterion) {
+    let mut document = Vec::new();
+    generate(&mut document, 0x2023_1201, 100_000).unwrap();
+    let document = String::from_utf8(document).unwrap();
+    let calibration = Calibration::new(DigitMode::NumericAndWords(Vocabulary::english()));
+
+    let mut group = c.benchmark_group("first and last digit of 100000 lines");
+
+    group.bench_function("find/rfind", |b| b.iter(|| {
+        black_box(&document).lines()
+            .for_each(|line| { black_box((naive_first_digit(line), naive_last_digit(line))); })
+    }));
+
+    group.bench_function("scanner", |b| b.iter(|| {
+        black_box(&document).lines()
+            .for_each(|line| { black_box((calibration.get_first_digit(line), calibration.get_last_digit(line))); })
+    }));
+
+    group.finish();
+}
+
+fn summary(c: &mut Criterion) {
+    // about 40 MB
+    let mut document = Vec::new();
+    generate(&mut document, 0x2023_1201, 1_000_000).unwrap();
+    let calibration = Calibration::new(DigitMode::NumericAndWords(Vocabulary::english()));
+    let threads = std::thread::available_parallelism().map_or(4, |threads| threads.get());
+
+    let mut group = c.benchmark_group("summary of 1000000 lines");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(document.len() as u64));
+
+    group.bench_function("string, lines", |b| b.iter(|| {
+        let document = String::from_utf8(black_box(&document).clone()).unwrap();
+        document.lines()
+            .filter_map(|line| calibration.get_calibration_value(line))
+            .map(|value| value as u64)
+            .sum::<u64>()
+    }));
+
+    group.bench_function("streaming", |b| b.iter(|| {
+        calibration.summary(black_box(document.as_slice())).unwrap()
+    }));
+
+    group.bench_function(format!("parallel, {threads} threads"), |b| b.iter(|| {
+        calibration.summary_parallel(black_box(document.as_slice()), threads).unwrap()
+    }));
+
+    group.finish();
+}
+
+criterion_group!(benches, scanner, summary);
+criterion_main!(benches);
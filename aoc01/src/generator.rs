@@ -0,0 +1,99 @@
+use std::io::{self, Write};
+
+static NUMBERS: [(&str, &str); 9] = [
+    ("1", "one"), ("2", "two"), ("3", "three"), ("4", "four"), ("5", "five"),
+    ("6", "six"), ("7", "seven"), ("8", "eight"), ("9", "nine"),
+];
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    // sums of the calibration values, when only digits count and when spelled ones do as well
+    pub part1: u64,
+    pub part2: u64,
+}
+
+impl seeded::Answers for Answers {
+    fn parts(&self) -> Vec<u128> { vec![self.part1.into(), self.part2.into()] }
+}
+
+pub fn generate<W: Write>(mut writer: W, seed: u64, lines: usize) -> io::Result<Answers> {
+    // Lines of 20 to 60 characters, mostly random letters,
+    // sprinkled with digits and spelled numbers (overlapping ones included).
+    // Documents of any size can be written without holding them in memory.
+    // The answers are worked out line by line on the way, the slow and simple way.
+    let mut random = seeded::random(seed);
+
+    let mut answers = Answers::default();
+    let mut line = Vec::new();
+    for _ in 0..lines {
+        line.clear();
+        let length = 20 + random(40);
+        while line.len() < length {
+            match random(10) {
+                0 => line.extend_from_slice(NUMBERS[random(9)].0.as_bytes()),
+                1 | 2 => line.extend_from_slice(NUMBERS[random(9)].1.as_bytes()),
+                _ => line.push(b'a' + random(26) as u8),
+            }
+        }
+        // every line needs at least one digit
+        line.extend_from_slice(NUMBERS[random(9)].0.as_bytes());
+
+        answers.part1 += calibration_value(&line, false);
+        answers.part2 += calibration_value(&line, true);
+
+        line.push(b'\n');
+        writer.write_all(&line)?;
+    }
+
+    writer.flush()?;
+    Ok(answers)
+}
+
+fn calibration_value(line: &[u8], words: bool) -> u64 {
+    // Tries every number at every position of the line
+    let digits: Vec<u64> = (0..line.len())
+        .filter_map(|i| (1..).zip(NUMBERS).find_map(|(digit, (number, word))| {
+            let found = line[i..].starts_with(number.as_bytes())
+                || words && line[i..].starts_with(word.as_bytes());
+            found.then_some(digit)
+        }))
+        .collect();
+
+    match (digits.first(), digits.last()) {
+        (Some(first), Some(last)) => first * 10 + last,
+        _ => 0,
+    }
+}
+
+
+#[cfg(test)]
+mod synthetic {
+    use std::io::Cursor;
+
+    use crate::{Calibration, DigitMode};
+    use crate::generator::generate;
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn generate_lines() {
+        let mut document = Vec::new();
+        generate(&mut document, 42, 1000).unwrap();
+
+        let document = String::from_utf8(document).unwrap();
+        assert_eq!(document.lines().count(), 1000);
+        assert!(document.lines().all(|line| line.chars().any(|c| c.is_ascii_digit())));
+        // spelled numbers sharing a letter, which part 2 has to read both of
+        assert!(["oneight", "twone", "threeight", "fiveight", "sevenine", "eightwo", "eighthree", "nineight"].iter()
+            .any(|overlap| document.contains(overlap)));
+    }
+
+    #[test]
+    fn generate_answers() {
+        let mut document = Vec::new();
+        let answers = generate(&mut document, 7, 10_000).unwrap();
+
+        let sum = |mode| Calibration::new(mode).summary(Cursor::new(&document)).unwrap().sum;
+        assert_eq!(sum(DigitMode::Numeric), answers.part1);
+        assert_eq!(sum(DigitMode::NumericAndWords(Vocabulary::english())), answers.part2);
+    }
+}
@@ -0,0 +1,484 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::thread;
+
+use scanner::{Scanner, Token};
+use vocabulary::Vocabulary;
+
+pub mod generator;
+pub mod scanner;
+pub mod vocabulary;
+
+// bytes read for each worker when summarising in parallel
+const CHUNK_SIZE: usize = 1 << 20;
+
+pub enum DigitMode {
+    // only digits count (part 1)
+    Numeric,
+    // digits and their spelled forms count (part 2)
+    NumericAndWords(Vocabulary),
+}
+
+pub struct Calibration {
+    // Extracts calibration values,
+    // recognising digits and, depending on the mode, spelled digits
+    scanner: Scanner,
+}
+
+impl Calibration {
+    pub fn new(mode: DigitMode) -> Self {
+        let scanner = match mode {
+            DigitMode::Numeric => Vocabulary::default().scanner(),
+            DigitMode::NumericAndWords(vocabulary) => vocabulary.scanner(),
+        };
+
+        Self { scanner }
+    }
+
+    pub fn get_calibration_value_sum(&self, path: &str) -> io::Result<u64> {
+        Ok(self.get_calibration_summary(path)?.sum)
+    }
+
+    pub fn get_calibration_summary(&self, path: &str) -> io::Result<Summary> {
+        self.summary(BufReader::new(File::open(path)?))
+    }
+
+    pub fn get_calibration_summary_parallel(&self, path: &str, threads: usize) -> io::Result<Summary> {
+        self.summary_parallel(File::open(path)?, threads)
+    }
+
+    pub fn values<R: BufRead>(&self, reader: R) -> Values<'_, R> {
+        Values { calibration: self, reader, line: Vec::new() }
+    }
+
+    pub fn summary<R: BufRead>(&self, reader: R) -> io::Result<Summary> {
+        let mut summary = Summary::default();
+
+        for (i, value) in self.values(reader).enumerate() {
+            match value? {
+                Some(value) => {
+                    summary.sum += value as u64;
+                    summary.valid += 1;
+                }
+                None => summary.without_digits.push(i + 1),
+            }
+        }
+
+        Ok(summary)
+    }
+
+    pub fn summary_parallel<R: Read>(&self, mut reader: R, threads: usize) -> io::Result<Summary> {
+        // The input is cut into chunks of whole lines, one per thread,
+        // which are summarised at the same time and then merged in order.
+        // The chunk buffers are reused, so memory stays at about threads * CHUNK_SIZE.
+        let mut chunks: Vec<Vec<u8>> = vec![Vec::new(); threads.max(1)];
+        let mut rest = Vec::new();
+        let mut summary = Summary::default();
+        let mut more = true;
+
+        while more {
+            let mut filled = 0;
+            while more && filled < chunks.len() {
+                more = read_chunk(&mut reader, &mut chunks[filled], &mut rest)?;
+                filled += 1;
+            }
+
+            let summaries: Vec<io::Result<Summary>> = thread::scope(|scope| {
+                let workers: Vec<_> = chunks[..filled].iter()
+                    .map(|chunk| scope.spawn(|| self.summary(chunk.as_slice())))
+                    .collect();
+                workers.into_iter().map(|worker| worker.join().unwrap()).collect()
+            });
+
+            for chunk_summary in summaries {
+                summary.append(chunk_summary?);
+            }
+        }
+
+        Ok(summary)
+    }
+
+    pub fn get_calibration_value(&self, line: &str) -> Option<i32> {
+        self.value(line.as_bytes())
+    }
+
+    fn value(&self, line: &[u8]) -> Option<i32> {
+        let (first, last) = self.scanner.first_last(line)?;
+        Some(first.digit as i32 * 10 + last.digit as i32)
+    }
+
+    pub fn get_two_digit_number(&self, line: &str) -> Option<String> {
+        let mut ret = String::new();
+        ret.push(self.get_first_digit(line)?);
+        ret.push(self.get_last_digit(line)?);
+
+        Some(ret)
+    }
+
+    pub fn get_digit_tokens(&self, line: &str) -> Vec<Token> {
+        self.scanner.tokens(line)
+    }
+
+    pub fn get_first_digit(&self, line: &str) -> Option<char> {
+        self.scanner.first(line).map(|token| char::from(b'0' + token.digit))
+    }
+
+    pub fn get_last_digit(&self, line: &str) -> Option<char> {
+        self.scanner.last(line).map(|token| char::from(b'0' + token.digit))
+    }
+}
+
+pub struct Values<'a, R> {
+    // The calibration value of every line, read one line at a time into the same buffer
+    calibration: &'a Calibration,
+    reader: R,
+    line: Vec<u8>,
+}
+
+impl<R: BufRead> Iterator for Values<'_, R> {
+    type Item = io::Result<Option<i32>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.line.clear();
+        match self.reader.read_until(b'\n', &mut self.line) {
+            Ok(0) => None,
+            Ok(_) => {
+                let line = self.line.strip_suffix(b"\n").unwrap_or(&self.line);
+                let line = line.strip_suffix(b"\r").unwrap_or(line);
+                Some(Ok(self.calibration.value(line)))
+            }
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
+
+fn read_chunk<R: Read>(reader: &mut R, chunk: &mut Vec<u8>, rest: &mut Vec<u8>) -> io::Result<bool> {
+    // Fills the chunk with the rest of the previous chunk and about CHUNK_SIZE more bytes,
+    // up to and including the last line break. What follows it is kept for the next chunk.
+    // Returns whether there may be more input.
+    chunk.clear();
+    chunk.append(rest);
+
+    loop {
+        let start = chunk.len();
+        let read = reader.by_ref().take(CHUNK_SIZE as u64).read_to_end(chunk)?;
+        if read < CHUNK_SIZE { return Ok(false); }
+
+        // a line longer than a chunk makes the chunk grow
+        if let Some(newline) = chunk[start..].iter().rposition(|&byte| byte == b'\n') {
+            rest.extend_from_slice(&chunk[start + newline + 1..]);
+            chunk.truncate(start + newline + 1);
+            return Ok(true);
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Summary {
+    // sum of the calibration values of all lines that have some
+    pub sum: u64,
+    pub valid: usize,
+    // numbers (starting at 1) of all lines without any digit
+    pub without_digits: Vec<usize>,
+}
+
+impl Summary {
+    pub fn lines(&self) -> usize {
+        self.valid + self.without_digits.len()
+    }
+
+    fn append(&mut self, next: Summary) {
+        // Adds the summary of the lines following the ones summarised so far
+        let offset = self.lines();
+        self.sum += next.sum;
+        self.valid += next.valid;
+        self.without_digits.extend(next.without_digits.into_iter().map(|line| line + offset));
+    }
+}
+
+
+#[cfg(test)]
+mod calibration_value {
+    // Both parts share the same tests:
+    // Lines with digits only must be read the same way in both modes,
+    // lines with spelled digits only differ in the second mode.
+    use crate::{Calibration, DigitMode};
+    use crate::generator::generate;
+    use crate::vocabulary::Vocabulary;
+
+    fn numeric() -> Calibration { Calibration::new(DigitMode::Numeric) }
+
+    fn words() -> Calibration { Calibration::new(DigitMode::NumericAndWords(Vocabulary::english())) }
+
+    static TESTEES: [&str; 4] = [
+        "a1bc2",
+        "pqr3stu8vwx",
+        "a1b2c3d4e5f",
+        "treb7uchet",
+    ];
+
+    static CALIBRATION_VALUES_SUM: u64 = 142;
+    static CALIBRATION_VALUES: [i32; 4] = [12, 38, 15, 77, ];
+    static TWO_DIGIT_NUMBERS: [&str; 4] = ["12", "38", "15", "77", ];
+    static FIRST_DIGITS: [char; 4] = ['1', '3', '1', '7', ];
+    static LAST_DIGITS: [char; 4] = ['2', '8', '5', '7', ];
+
+    static TESTEES_WORDS: [&str; 7] = [
+        "two1nine",
+        "eightwothree",
+        "abcone2threexyz",
+        "xtwone3four",
+        "4nineeightseven2",
+        "zoneight234",
+        "7pqrstsixteen",
+    ];
+
+    static CALIBRATION_VALUES_SUM_WORDS: u64 = 281;
+    static CALIBRATION_VALUES_WORDS: [i32; 7] = [29, 83, 13, 24, 42, 14, 76, ];
+    static TWO_DIGIT_NUMBERS_WORDS: [&str; 7] = ["29", "83", "13", "24", "42", "14", "76", ];
+    static FIRST_DIGITS_WORDS: [char; 7] = ['2', '8', '1', '2', '4', '1', '7', ];
+    static LAST_DIGITS_WORDS: [char; 7] = ['9', '3', '3', '4', '2', '4', '6', ];
+
+    // the same lines, when only digits count
+    static CALIBRATION_VALUES_WORDS_NUMERIC: [Option<i32>; 7] = [
+        Some(11), None, Some(22), Some(33), Some(42), Some(24), Some(77),
+    ];
+
+    #[test]
+    fn get_calibration_value_sum() {
+        assert_eq!(numeric().get_calibration_value_sum("src/example").unwrap(), CALIBRATION_VALUES_SUM);
+        assert_eq!(words().get_calibration_value_sum("src/example").unwrap(), CALIBRATION_VALUES_SUM);
+        assert_eq!(words().get_calibration_value_sum("src/example2").unwrap(), CALIBRATION_VALUES_SUM_WORDS);
+    }
+
+    #[test]
+    fn get_calibration_value() {
+        for calibration in [numeric(), words()] {
+            for (testee, expect) in TESTEES.into_iter().zip(CALIBRATION_VALUES.iter()) {
+                assert_eq!(calibration.get_calibration_value(testee), Some(*expect));
+            }
+        }
+
+        for (testee, expect) in TESTEES_WORDS.into_iter().zip(CALIBRATION_VALUES_WORDS.iter()) {
+            assert_eq!(words().get_calibration_value(testee), Some(*expect));
+        }
+
+        for (testee, expect) in TESTEES_WORDS.into_iter().zip(CALIBRATION_VALUES_WORDS_NUMERIC.iter()) {
+            assert_eq!(numeric().get_calibration_value(testee), *expect);
+        }
+    }
+
+    #[test]
+    fn get_two_digit_number() {
+        for calibration in [numeric(), words()] {
+            for (testee, expect) in TESTEES.into_iter().zip(TWO_DIGIT_NUMBERS.iter()) {
+                assert_eq!(calibration.get_two_digit_number(testee), Some(expect.to_string()));
+            }
+        }
+
+        for (testee, expect) in TESTEES_WORDS.into_iter().zip(TWO_DIGIT_NUMBERS_WORDS.iter()) {
+            assert_eq!(words().get_two_digit_number(testee), Some(expect.to_string()));
+        }
+    }
+
+    #[test]
+    fn get_digit_tokens() {
+        let digits: Vec<u8> = words().get_digit_tokens("eightwothree")
+            .iter().map(|token| token.digit).collect();
+        assert_eq!(digits, vec![8, 2, 3]);
+
+        assert_eq!(numeric().get_digit_tokens("eightwothree"), vec![]);
+    }
+
+    #[test]
+    fn get_first_digit() {
+        for calibration in [numeric(), words()] {
+            for (testee, expect) in TESTEES.into_iter().zip(FIRST_DIGITS.iter()) {
+                assert_eq!(calibration.get_first_digit(testee), Some(*expect));
+            }
+        }
+
+        for (testee, expect) in TESTEES_WORDS.into_iter().zip(FIRST_DIGITS_WORDS.iter()) {
+            assert_eq!(words().get_first_digit(testee), Some(*expect));
+        }
+    }
+
+    #[test]
+    fn get_last_digit() {
+        for calibration in [numeric(), words()] {
+            for (testee, expect) in TESTEES.into_iter().zip(LAST_DIGITS.iter()) {
+                assert_eq!(calibration.get_last_digit(testee), Some(*expect));
+            }
+        }
+
+        for (testee, expect) in TESTEES_WORDS.into_iter().zip(LAST_DIGITS_WORDS.iter()) {
+            assert_eq!(words().get_last_digit(testee), Some(*expect));
+        }
+    }
+
+    #[test]
+    fn vocabulary() {
+        let german = Calibration::new(DigitMode::NumericAndWords(Vocabulary::german()));
+        assert_eq!(german.get_calibration_value("xsiebenullfünfx"), Some(75));
+        assert_eq!(german.get_calibration_value("achteins"), Some(81));
+
+        let german = Calibration::new(DigitMode::NumericAndWords(Vocabulary::german().with_zero()));
+        assert_eq!(german.get_calibration_value("xsiebenullfünfx"), Some(75));
+        assert_eq!(german.get_calibration_value("nullzweidrei"), Some(3));
+
+        let french = Calibration::new(DigitMode::NumericAndWords(Vocabulary::french().ignoring_case()));
+        assert_eq!(french.get_calibration_value("QuatreVingtDix-Neuf"), Some(49));
+    }
+
+    #[test]
+    fn without_digits() {
+        for calibration in [numeric(), words()] {
+            assert_eq!(calibration.get_first_digit("abc"), None);
+            assert_eq!(calibration.get_last_digit("abc"), None);
+            assert_eq!(calibration.get_two_digit_number("abc"), None);
+            assert_eq!(calibration.get_calibration_value("abc"), None);
+            assert_eq!(calibration.get_calibration_value("zero"), None);
+            assert_eq!(calibration.get_calibration_value(""), None);
+        }
+    }
+
+    #[test]
+    fn summary() {
+        let document = "two1nine\nabc\neightwothree\n\n7pqrstsixteen";
+
+        let summary = words().summary(document.as_bytes()).unwrap();
+        assert_eq!(summary.sum, 29 + 83 + 76);
+        assert_eq!(summary.valid, 3);
+        assert_eq!(summary.without_digits, vec![2, 4]);
+
+        let summary = numeric().summary(document.as_bytes()).unwrap();
+        assert_eq!(summary.sum, 11 + 77);
+        assert_eq!(summary.valid, 2);
+        assert_eq!(summary.without_digits, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn values() {
+        let values: Vec<Option<i32>> = numeric().values("1abc2\r\nabc\r\ntreb7uchet\r\n".as_bytes())
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(values, vec![Some(12), None, Some(77)]);
+    }
+
+    #[test]
+    fn summary_parallel() {
+        for calibration in [numeric(), words()] {
+            let mut document = Vec::new();
+            generate(&mut document, 7, 100_000).unwrap();
+            // lines without digits, also right at the start and at the end
+            document.splice(0..0, b"abc\n".iter().copied());
+            document.extend_from_slice(b"xyz\n\n");
+
+            let summary = calibration.summary(document.as_slice()).unwrap();
+            assert_eq!(summary.lines(), 100_003);
+            assert_eq!(summary.without_digits[0], 1);
+            assert_eq!(summary.without_digits[summary.without_digits.len() - 2..], [100_002, 100_003]);
+
+            for threads in [1, 2, 3, 8] {
+                assert_eq!(calibration.summary_parallel(document.as_slice(), threads).unwrap(), summary);
+            }
+        }
+    }
+
+    #[test]
+    fn summary_parallel_long_lines() {
+        // lines longer than a chunk, with and without a final line break
+        let long = "x".repeat(3 * crate::CHUNK_SIZE);
+        let document = format!("1{long}\n{long}\n{long}2{long}");
+
+        let summary = numeric().summary_parallel(document.as_bytes(), 2).unwrap();
+        assert_eq!(summary, crate::Summary { sum: 11 + 22, valid: 2, without_digits: vec![2] });
+        assert_eq!(numeric().summary_parallel(format!("{document}\n").as_bytes(), 2).unwrap(), summary);
+        assert_eq!(numeric().summary_parallel("".as_bytes(), 2).unwrap(), crate::Summary::default());
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use std::io::Cursor;
+
+    use proptest::prelude::*;
+
+    use crate::{Calibration, DigitMode};
+    use crate::vocabulary::Vocabulary;
+
+    static WORDS: [&str; 10] = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+    fn line() -> impl Strategy<Value=(String, Vec<u8>)> {
+        // A calibration line of digits and spelled digits, padded with letters no digit is spelled with,
+        // together with the digits in it
+        let filler = "[xqyz]{0,3}";
+        let piece = (1..=9u8, any::<bool>(), filler)
+            .prop_map(|(digit, spelled, filler)| {
+                let piece = if spelled { WORDS[digit as usize].to_string() } else { digit.to_string() };
+                (piece + &filler, digit)
+            });
+
+        (filler, prop::collection::vec(piece, 0..8))
+            .prop_map(|(mut line, pieces)| {
+                let mut digits = Vec::new();
+                for (piece, digit) in pieces {
+                    line.push_str(&piece);
+                    digits.push(digit);
+                }
+                (line, digits)
+            })
+    }
+
+    fn words() -> Calibration { Calibration::new(DigitMode::NumericAndWords(Vocabulary::english())) }
+
+    proptest! {
+        #[test]
+        fn first_and_last_digit(generated in line()) {
+            let (line, digits) = generated;
+            let expect = digits.first().zip(digits.last()).map(|(&first, &last)| (first * 10 + last) as i32);
+
+            prop_assert_eq!(words().get_calibration_value(&line), expect);
+        }
+
+        #[test]
+        fn numeric_takes_ascii_digits(line in "[a-z0-9]{0,30}") {
+            let digits: Vec<char> = line.chars().filter(|c| ('1'..='9').contains(c)).collect();
+            let expect = digits.first().zip(digits.last())
+                .map(|(first, last)| format!("{first}{last}").parse().unwrap());
+
+            prop_assert_eq!(Calibration::new(DigitMode::Numeric).get_calibration_value(&line), expect);
+        }
+
+        #[test]
+        fn first_last_agrees_with_tokens(line in "[onetwhrfuivsxg0-9]{0,30}") {
+            let scanner = Vocabulary::english().with_zero().scanner();
+            let tokens = scanner.tokens(&line);
+
+            let first = tokens.first().copied();
+            let last = tokens.iter().copied().max_by_key(|token| token.start);
+            prop_assert_eq!(scanner.first_last(line.as_bytes()), first.zip(last));
+            prop_assert_eq!(scanner.first(&line), first);
+            prop_assert_eq!(scanner.last(&line), last);
+        }
+
+        #[test]
+        fn summaries_agree(lines in prop::collection::vec(line(), 0..50), crlf in any::<bool>(), threads in 1..4usize) {
+            let calibration = words();
+            let text: String = lines.iter()
+                .map(|(line, _)| line.clone() + if crlf { "\r\n" } else { "\n" })
+                .collect();
+            let values: Vec<Option<i32>> = lines.iter().map(|(line, _)| calibration.get_calibration_value(line)).collect();
+
+            let summary = calibration.summary(Cursor::new(&text)).unwrap();
+            prop_assert_eq!(summary.sum, values.iter().flatten().map(|&value| value as u64).sum::<u64>());
+            prop_assert_eq!(summary.lines(), lines.len());
+            prop_assert_eq!(&summary.without_digits, &(1..).zip(&values)
+                .filter(|(_, value)| value.is_none())
+                .map(|(line, _)| line)
+                .collect::<Vec<usize>>());
+
+            prop_assert_eq!(calibration.summary_parallel(Cursor::new(&text), threads).unwrap(), summary);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::env::args;
+use std::thread::available_parallelism;
+
+use aoc01::{Calibration, DigitMode};
+use aoc01::generator::generate;
+use aoc01::vocabulary::Vocabulary;
+use seeded::generate_command;
+
+fn main() {
+    // Usage: aoc01 <input> [<threads>]
+    //        aoc01 generate <lines> [<seed>] > <input>    (the answers go to stderr)
+    let usage = "usage: aoc01 <input> [<threads>] | aoc01 generate <lines> [<seed>]";
+    let args: Vec<String> = args().collect();
+
+    if generate_command(&args[1..], usage, 1, |sizes, seed, writer| {
+        generate(writer, seed, sizes[0].parse().expect("lines must be a number"))
+    }) {
+        return;
+    }
+
+    let input = args.get(1).expect(usage);
+    let threads = args.get(2).map_or_else(
+        || available_parallelism().map_or(1, |threads| threads.get()),
+        |threads| threads.parse().expect("threads must be a number"),
+    );
+
+    for (part, mode) in [(1, DigitMode::Numeric), (2, DigitMode::NumericAndWords(Vocabulary::english()))] {
+        let summary = Calibration::new(mode).get_calibration_summary_parallel(input, threads).unwrap();
+        println!("part {part}: {} ({} of {} lines without digits)",
+                 summary.sum, summary.without_digits.len(), summary.lines());
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    // byte positions within the line, end exclusive
+    pub start: usize,
+    pub end: usize,
+    pub digit: u8,
+}
+
+pub struct Scanner {
+    // Aho-Corasick automaton, compiled into a complete transition table:
+    // Reading a byte in a state always leads to the state of the longest pattern prefix,
+    // that is a suffix of everything read so far.
+    // This way, the line is read exactly once, and overlapping patterns ("eightwo") are all found.
+    transitions: Vec<[u32; 256]>,
+    // (digit, pattern length) of every pattern ending in a state
+    outputs: Vec<Vec<(u8, usize)>>,
+    longest: usize,
+}
+
+impl Scanner {
+    pub fn new(patterns: &[(&str, u8)]) -> Self {
+        let patterns: Vec<(String, u8)> = patterns.iter()
+            .map(|&(pattern, digit)| (pattern.to_string(), digit))
+            .collect();
+
+        Self::build(&patterns, false)
+    }
+
+    pub fn ignoring_case(patterns: &[(&str, u8)]) -> Self {
+        // ASCII letters are folded within the transition table, once it is complete.
+        // The few other letters ("fünf", "zéro") are looked for in every combination of upper and lower case.
+        let patterns: Vec<(String, u8)> = patterns.iter()
+            .flat_map(|&(pattern, digit)| Self::case_variants(pattern).into_iter()
+                .map(move |variant| (variant, digit)))
+            .collect();
+
+        Self::build(&patterns, true)
+    }
+
+    fn case_variants(pattern: &str) -> Vec<String> {
+        let mut variants = vec![String::new()];
+
+        for c in pattern.chars() {
+            if c.is_ascii() {
+                for variant in variants.iter_mut() { variant.push(c.to_ascii_lowercase()) }
+                continue;
+            }
+
+            // Some letters change into ASCII ones ("ß" into "SS"),
+            // which have to be lower case for the folding in the transition table to find them.
+            let lower: String = c.to_lowercase().map(|c| c.to_ascii_lowercase()).collect();
+            let upper: String = c.to_uppercase().map(|c| c.to_ascii_lowercase()).collect();
+
+            variants = variants.into_iter()
+                .flat_map(|variant| {
+                    if lower == upper {
+                        vec![variant + &lower]
+                    } else {
+                        vec![variant.clone() + &lower, variant + &upper]
+                    }
+                })
+                .collect();
+        }
+
+        variants
+    }
+
+    fn build(patterns: &[(String, u8)], ignore_case: bool) -> Self {
+        const NONE: u32 = u32::MAX;
+
+        // Build a trie of all patterns
+        //
+        // o -n-> on -e-> one
+        // t -w-> tw -o-> two
+        //   -h-> th -r-> thr -e-> thre -e-> three
+        let mut transitions = vec![[NONE; 256]];
+        let mut outputs = vec![Vec::new()];
+
+        for (pattern, digit) in patterns.iter().filter(|(pattern, _)| !pattern.is_empty()) {
+            let mut state = 0;
+            for &byte in pattern.as_bytes() {
+                if transitions[state][byte as usize] == NONE {
+                    transitions[state][byte as usize] = transitions.len() as u32;
+                    transitions.push([NONE; 256]);
+                    outputs.push(Vec::new());
+                }
+                state = transitions[state][byte as usize] as usize;
+            }
+            outputs[state].push((*digit, pattern.len()));
+        }
+
+        // Fill in the missing transitions, breadth first.
+        // A missing transition continues where the longest proper suffix of the state (its failure) would go.
+        // A state also ends every pattern its failure ends ("eightwo" ends in "two").
+        let mut failure = vec![0; transitions.len()];
+        let mut queue = VecDeque::new();
+
+        for next in transitions[0].iter_mut() {
+            match *next {
+                NONE => *next = 0,
+                _ => queue.push_back(*next as usize),
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            let inherited = outputs[failure[state]].clone();
+            outputs[state].extend(inherited);
+
+            let fallbacks = transitions[failure[state]];
+            for (next, fallback) in transitions[state].iter_mut().zip(fallbacks) {
+                match *next {
+                    NONE => *next = fallback,
+                    _ => {
+                        failure[*next as usize] = fallback as usize;
+                        queue.push_back(*next as usize);
+                    }
+                }
+            }
+        }
+
+        if ignore_case {
+            for transition in transitions.iter_mut() {
+                for upper in b'A'..=b'Z' {
+                    transition[upper as usize] = transition[upper.to_ascii_lowercase() as usize];
+                }
+            }
+        }
+
+        let longest = patterns.iter().map(|(pattern, _)| pattern.len()).max().unwrap_or(0);
+
+        Self { transitions, outputs, longest }
+    }
+
+    fn matches<'a>(&'a self, line: &'a [u8]) -> impl Iterator<Item=Token> + 'a {
+        // All matches, in order of their end
+        line.iter()
+            .scan(0, move |state, &byte| {
+                *state = self.transitions[*state][byte as usize] as usize;
+                Some(*state)
+            })
+            .enumerate()
+            .flat_map(move |(i, state)| self.outputs[state].iter()
+                .map(move |&(digit, length)| Token { start: i + 1 - length, end: i + 1, digit }))
+    }
+
+    pub fn tokens(&self, line: &str) -> Vec<Token> {
+        // All matches, in order of their start
+        let mut tokens: Vec<Token> = self.matches(line.as_bytes()).collect();
+        tokens.sort_by_key(|token| token.start);
+        tokens
+    }
+
+    pub fn first(&self, line: &str) -> Option<Token> {
+        // Matches are found in order of their end.
+        // A match found later can only start earlier if it is longer,
+        // so once a match ends the longest pattern length behind the best start,
+        // no other match can start before the best one.
+        let mut first: Option<Token> = None;
+
+        for token in self.matches(line.as_bytes()) {
+            match first {
+                Some(f) if token.end >= f.start + self.longest => break,
+                Some(f) if token.start >= f.start => {}
+                _ => first = Some(token),
+            }
+        }
+
+        first
+    }
+
+    pub fn last(&self, line: &str) -> Option<Token> {
+        self.matches(line.as_bytes())
+            .fold(None, |last, token| match last {
+                Some(l) if l.start > token.start => Some(l),
+                _ => Some(token),
+            })
+    }
+
+    pub fn first_last(&self, line: &[u8]) -> Option<(Token, Token)> {
+        // First and last match, found while reading the line once.
+        // Works on raw bytes, so lines need neither be copied nor checked to be UTF-8.
+        let mut state = 0;
+        let mut first_last: Option<(Token, Token)> = None;
+
+        for (i, &byte) in line.iter().enumerate() {
+            state = self.transitions[state][byte as usize] as usize;
+            for &(digit, length) in &self.outputs[state] {
+                let token = Token { start: i + 1 - length, end: i + 1, digit };
+                first_last = match first_last {
+                    Some((f, l)) => Some((
+                        if token.start < f.start { token } else { f },
+                        if token.start >= l.start { token } else { l },
+                    )),
+                    None => Some((token, token)),
+                };
+            }
+        }
+
+        first_last
+    }
+}
+
+
+#[cfg(test)]
+mod tokens {
+    use crate::scanner::{Scanner, Token};
+
+    fn digits(scanner: &Scanner, line: &str) -> Vec<u8> {
+        scanner.tokens(line).iter().map(|token| token.digit).collect()
+    }
+
+    #[test]
+    fn overlapping() {
+        let scanner = Scanner::new(&[("1", 1), ("one", 1), ("2", 2), ("two", 2), ("8", 8), ("eight", 8)]);
+
+        assert_eq!(scanner.tokens("eightwo"), vec![
+            Token { start: 0, end: 5, digit: 8 },
+            Token { start: 4, end: 7, digit: 2 },
+        ]);
+        assert_eq!(digits(&scanner, "twone"), vec![2, 1]);
+        assert_eq!(digits(&scanner, "oneight2twone"), vec![1, 8, 2, 2, 1]);
+        assert_eq!(digits(&scanner, "xyz"), vec![]);
+        assert_eq!(digits(&scanner, ""), vec![]);
+    }
+
+    #[test]
+    fn first_last() {
+        let scanner = Scanner::new(&[("1", 1), ("one", 1), ("2", 2), ("two", 2), ("8", 8), ("eight", 8)]);
+
+        assert_eq!(scanner.first("xtwone8").map(|t| t.digit), Some(2));
+        assert_eq!(scanner.last("xtwone8").map(|t| t.digit), Some(8));
+        assert_eq!(scanner.last("8xtwone").map(|t| t.digit), Some(1));
+        assert_eq!(scanner.first("xyz"), None);
+        assert_eq!(scanner.last("xyz"), None);
+
+        let digits = |line: &[u8]| scanner.first_last(line).map(|(f, l)| (f.digit, l.digit));
+        assert_eq!(digits(b"xtwone8"), Some((2, 8)));
+        assert_eq!(digits(b"8xtwone"), Some((8, 1)));
+        assert_eq!(digits(b"\xffeight\xfe"), Some((8, 8)));
+        assert_eq!(digits(b"xyz"), None);
+    }
+
+    #[test]
+    fn first_starts_earliest() {
+        // "abcdef" ends after "bc", but starts before it
+        let scanner = Scanner::new(&[("abcdef", 1), ("bc", 2)]);
+
+        assert_eq!(scanner.first("abcdef").map(|t| t.digit), Some(1));
+        assert_eq!(scanner.last("abcdef").map(|t| t.digit), Some(2));
+        assert_eq!(digits(&scanner, "abcdef"), vec![1, 2]);
+    }
+
+    #[test]
+    fn ignoring_case() {
+        let scanner = Scanner::ignoring_case(&[("Eight", 8), ("two", 2), ("fünf", 5)]);
+
+        assert_eq!(digits(&scanner, "EIGHTWO"), vec![8, 2]);
+        assert_eq!(digits(&scanner, "eIgHtWo"), vec![8, 2]);
+        assert_eq!(digits(&scanner, "FÜNF fünf Fünf FüNF"), vec![5, 5, 5, 5]);
+
+        // byte positions still refer to the line as it is
+        assert_eq!(scanner.first("xxFÜNF"), Some(Token { start: 2, end: 7, digit: 5 }));
+
+        let scanner = Scanner::new(&[("eight", 8), ("two", 2)]);
+        assert_eq!(digits(&scanner, "EIGHTWO"), vec![]);
+    }
+}
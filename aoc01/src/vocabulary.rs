@@ -0,0 +1,222 @@
+use std::fmt;
+
+use crate::scanner::Scanner;
+
+static DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+static ENGLISH: [&str; 10] = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+static GERMAN: [&str; 10] = ["null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun"];
+static FRENCH: [&str; 10] = ["zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf"];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Vocabulary {
+    // Digits are always recognised, spelled digits are looked up in the words.
+    // Zero (as digit or spelled) only counts if asked for, as the puzzle only knows one to nine.
+    words: Vec<(String, u8)>,
+    zero: bool,
+    ignore_case: bool,
+}
+
+impl Vocabulary {
+    pub fn english() -> Self { Self::from_words(ENGLISH) }
+
+    pub fn german() -> Self { Self::from_words(GERMAN) }
+
+    pub fn french() -> Self { Self::from_words(FRENCH) }
+
+    pub fn language(name: &str) -> Option<Self> {
+        match name {
+            "english" => Some(Self::english()),
+            "german" => Some(Self::german()),
+            "french" => Some(Self::french()),
+            "none" => Some(Self::default()),
+            _ => None
+        }
+    }
+
+    fn from_words(words: [&str; 10]) -> Self {
+        let words = words.iter()
+            .zip(0..)
+            .map(|(word, digit)| (word.to_string(), digit))
+            .collect();
+
+        Self { words, ..Self::default() }
+    }
+
+    pub fn with_word(mut self, word: &str, digit: u8) -> Result<Self, InvalidDigit> {
+        if digit > 9 { return Err(InvalidDigit(digit)); }
+        self.words.push((word.to_string(), digit));
+        Ok(self)
+    }
+
+    pub fn with_zero(mut self) -> Self {
+        self.zero = true;
+        self
+    }
+
+    pub fn ignoring_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+
+    pub fn patterns(&self) -> Vec<(&str, u8)> {
+        DIGITS.iter()
+            .zip(0..)
+            .map(|(digit_str, digit)| (*digit_str, digit))
+            .chain(self.words.iter().map(|(word, digit)| (word.as_str(), *digit)))
+            .filter(|&(_, digit)| self.zero || digit != 0)
+            .collect()
+    }
+
+    pub fn scanner(&self) -> Scanner {
+        if self.ignore_case {
+            Scanner::ignoring_case(&self.patterns())
+        } else {
+            Scanner::new(&self.patterns())
+        }
+    }
+
+    pub fn from_config(s: &str) -> Result<Self, ConfigError> {
+        // # comments start with a hash
+        // language = german      (english, german, french or none, defaults to english)
+        // zero = true            (also count zero, defaults to false)
+        // ignore_case = true     (defaults to false)
+        // 7 = sieben, siebn      (additional spellings of a digit)
+        let mut vocabulary = Self::english();
+        let mut words: Vec<(String, u8)> = Vec::new();
+        let mut zero = false;
+        let mut ignore_case = false;
+
+        for (i, line) in s.lines().enumerate() {
+            let error = |message: String| ConfigError { line: i + 1, message };
+
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() { continue; }
+
+            let (key, value) = line.split_once('=')
+                .ok_or_else(|| error(format!("expected 'key = value', got '{line}'")))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            let flag = |value: &str| match value {
+                "true" => Ok(true),
+                "false" => Ok(false),
+                _ => Err(error(format!("expected true or false for {key}, got '{value}'")))
+            };
+
+            match key {
+                "language" => vocabulary = Self::language(value)
+                    .ok_or_else(|| error(format!("unknown language '{value}'")))?,
+                "zero" => zero = flag(value)?,
+                "ignore_case" => ignore_case = flag(value)?,
+                _ => {
+                    let digit: u8 = key.parse().ok()
+                        .filter(|digit| *digit <= 9)
+                        .ok_or_else(|| error(format!("unknown key '{key}'")))?;
+
+                    for word in value.split(',').map(str::trim).filter(|word| !word.is_empty()) {
+                        words.push((word.to_string(), digit));
+                    }
+                }
+            }
+        }
+
+        vocabulary.words.append(&mut words);
+        vocabulary.zero = zero;
+        vocabulary.ignore_case = ignore_case;
+
+        Ok(vocabulary)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ConfigError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidDigit(pub u8);
+
+impl fmt::Display for InvalidDigit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is not a digit, expected 0 to 9", self.0)
+    }
+}
+
+impl std::error::Error for InvalidDigit {}
+
+
+#[cfg(test)]
+mod config {
+    use std::fs::read_to_string;
+
+    use crate::vocabulary::{ConfigError, InvalidDigit, Vocabulary};
+
+    #[test]
+    fn patterns() {
+        let english = Vocabulary::english();
+        let patterns = english.patterns();
+        assert!(patterns.contains(&("1", 1)));
+        assert!(patterns.contains(&("nine", 9)));
+        assert!(!patterns.contains(&("0", 0)));
+        assert!(!patterns.contains(&("zero", 0)));
+
+        let english = Vocabulary::english().with_zero();
+        let patterns = english.patterns();
+        assert!(patterns.contains(&("0", 0)));
+        assert!(patterns.contains(&("zero", 0)));
+
+        assert_eq!(Vocabulary::default().patterns().len(), 9);
+    }
+
+    #[test]
+    fn from_config() {
+        assert_eq!(Vocabulary::from_config(""), Ok(Vocabulary::english()));
+
+        assert_eq!(
+            Vocabulary::from_config(&read_to_string("src/example_vocabulary").unwrap()),
+            Ok(Vocabulary::german().with_word("siebn", 7).unwrap().with_word("zwo", 2).unwrap().with_zero().ignoring_case())
+        );
+
+        assert_eq!(
+            Vocabulary::from_config("language = none\n1 = uno\n2 = dos, duo"),
+            Ok(Vocabulary::default().with_word("uno", 1).unwrap().with_word("dos", 2).unwrap().with_word("duo", 2).unwrap())
+        );
+    }
+
+    #[test]
+    fn from_config_errors() {
+        assert_eq!(Vocabulary::from_config("\nlanguage = klingon"),
+                   Err(ConfigError { line: 2, message: "unknown language 'klingon'".to_string() }));
+        assert_eq!(Vocabulary::from_config("zero = yes"),
+                   Err(ConfigError { line: 1, message: "expected true or false for zero, got 'yes'".to_string() }));
+        assert_eq!(Vocabulary::from_config("10 = ten"),
+                   Err(ConfigError { line: 1, message: "unknown key '10'".to_string() }));
+        assert_eq!(Vocabulary::from_config("one"),
+                   Err(ConfigError { line: 1, message: "expected 'key = value', got 'one'".to_string() }));
+    }
+
+    #[test]
+    fn with_word() {
+        assert_eq!(Vocabulary::default().with_word("ten", 10), Err(InvalidDigit(10)));
+        assert_eq!(InvalidDigit(10).to_string(), "10 is not a digit, expected 0 to 9");
+    }
+
+    #[test]
+    fn ignoring_case_folds_to_ascii() {
+        // "ß" is "SS" in upper case, which has to be found in lower and mixed case too
+        let vocabulary = Vocabulary::from_config("language = none\nignore_case = true\n3 = dreiß").unwrap();
+        let scanner = vocabulary.scanner();
+
+        let digits = |line: &str| scanner.tokens(line).iter().map(|token| token.digit).collect::<Vec<_>>();
+        assert_eq!(digits("dreiß DREISS Dreiss DREIß"), vec![3, 3, 3, 3]);
+    }
+}
@@ -0,0 +1,33 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc02::{parse_input, Bag, Repeated};
+use aoc02::generator::generate;
+
+fn phases(c: &mut Criterion) {
+    let mut record = Vec::new();
+    generate(&mut record, 0x2023_1202, 10_000).unwrap();
+    let record = String::from_utf8(record).unwrap();
+
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("aoc02 10000 games", |b| b.iter(|| parse_input(black_box(&record), Repeated::Reject).unwrap()));
+    group.finish();
+
+    let games = parse_input(&record, Repeated::Reject).unwrap();
+    let bag = Bag::from([("red", 12), ("green", 13), ("blue", 14)]);
+
+    let mut group = c.benchmark_group("solve");
+    group.bench_function("aoc02a 10000 games", |b| b.iter(|| {
+        black_box(&games).iter()
+            .filter(|game| game.possible(&bag))
+            .fold(0, |acc, game| acc + game.id)
+    }));
+    group.bench_function("aoc02b 10000 games", |b| b.iter(|| {
+        black_box(&games).iter().map(|game| game.power()).sum::<u32>()
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, phases);
+criterion_main!(benches);
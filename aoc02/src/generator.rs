@@ -0,0 +1,83 @@
+use std::io::{self, Write};
+
+use crate::COLORS;
+
+// the Bag of part 1: 12 red, 13 green, 14 blue
+static BAG: [u32; 3] = [12, 13, 14];
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    // sum of the ids of the Games possible with 12 red, 13 green and 14 blue cubes
+    pub part1: u64,
+    // sum of the powers of the smallest Bags of all Games, 0 for a Game missing a color
+    pub part2: u64,
+}
+
+impl seeded::Answers for Answers {
+    fn parts(&self) -> Vec<u128> { vec![self.part1.into(), self.part2.into()] }
+}
+
+pub fn generate<W: Write>(mut writer: W, seed: u64, games: usize) -> io::Result<Answers> {
+    // Games of 1 to 6 Rounds, each Round drawing 1 to 20 cubes of some of the colors, in any order.
+    // The answers are worked out from the counts drawn, without parsing anything.
+    let mut random = seeded::random(seed);
+
+    let mut answers = Answers::default();
+    for id in 1..=games {
+        // the most cubes of every color drawn in a Round, 0 if never drawn
+        let mut most = [0; 3];
+
+        let rounds: Vec<String> = (0..1 + random(6))
+            .map(|_| {
+                let mut colors = vec![0, 1, 2];
+                // any of the orders of the three colors, then some of them drawn
+                colors.swap(0, random(3));
+                colors.swap(1, 1 + random(2));
+                colors.truncate(1 + random(3));
+
+                let cubes: Vec<String> = colors.into_iter()
+                    .map(|color| {
+                        let count = 1 + random(20) as u32;
+                        most[color] = most[color].max(count);
+                        format!("{count} {}", COLORS[color])
+                    })
+                    .collect();
+                cubes.join(", ")
+            })
+            .collect();
+        writeln!(writer, "Game {id}: {}", rounds.join("; "))?;
+
+        if most.iter().zip(BAG).all(|(&count, cubes)| count <= cubes) {
+            answers.part1 += id as u64;
+        }
+        answers.part2 += most.iter().map(|&count| count as u64).product::<u64>();
+    }
+
+    writer.flush()?;
+    Ok(answers)
+}
+
+
+#[cfg(test)]
+mod synthetic {
+    use crate::{parse_input, Bag, Repeated};
+    use crate::generator::generate;
+
+    #[test]
+    fn generate_answers() {
+        let mut record = Vec::new();
+        let answers = generate(&mut record, 42, 1000).unwrap();
+
+        let games = parse_input(&String::from_utf8(record).unwrap(), Repeated::Reject).unwrap();
+        assert_eq!(games.len(), 1000);
+
+        let bag = Bag::from([("red", 12), ("green", 13), ("blue", 14)]);
+        let possible: u32 = games.iter().filter(|game| game.possible(&bag)).map(|game| game.id).sum();
+        assert_eq!(possible as u64, answers.part1);
+        assert_eq!(games.iter().map(|game| game.power() as u64).sum::<u64>(), answers.part2);
+
+        // both parts have Games to leave out: too many cubes, and colors never drawn
+        assert!(games.iter().any(|game| !game.possible(&bag)));
+        assert!(games.iter().any(|game| game.power() == 0));
+    }
+}
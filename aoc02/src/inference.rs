@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+
+use crate::{minimal_bag, Bag, Game};
+
+// Half the 95% quantile of the chi-squared distribution with one degree of freedom:
+// counts whose log-likelihood is within this of the best one are plausible
+const CONFIDENCE_DROP: f64 = 1.92;
+
+#[derive(Debug, PartialEq)]
+pub struct Estimate {
+    // the most likely Bag, every Game drawn from it
+    pub bag: Bag,
+    pub log_likelihood: f64,
+    // plausible numbers of cubes in the Bag
+    pub total: Interval,
+    // plausible counts of every color, within the most likely Bags of plausible sizes
+    pub intervals: BTreeMap<String, Interval>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval {
+    pub low: u32,
+    // None, if Bags up to the size limit are all plausible
+    pub high: Option<u32>,
+}
+
+pub fn log_likelihood(bag: &Bag, games: &[Game]) -> f64 {
+    // Every Round draws its cubes at once (without replacement) from the full Bag,
+    // and puts them back afterwards. The chance of drawing a Round is hypergeometric:
+    //   product over colors of C(cubes in bag, cubes drawn) / C(all cubes in bag, all cubes drawn)
+    let total: u32 = bag.cubes.values().sum();
+    let mut log_likelihood = 0.0;
+
+    for round in games.iter().flat_map(|game| &game.rounds) {
+        if !round.possible(bag) { return f64::NEG_INFINITY; }
+
+        let drawn: u32 = round.cubes.values().sum();
+        log_likelihood -= log_binomial(total, drawn);
+        for (color, &count) in &round.cubes {
+            log_likelihood += log_binomial(bag.count(color), count);
+        }
+    }
+
+    log_likelihood
+}
+
+pub fn estimate(games: &[Game], limit: u32) -> Option<Estimate> {
+    // Maximum likelihood estimate of the Bag all Games were drawn from,
+    // considering only colors drawn at all and Bags of at most `limit` cubes.
+    //
+    // The draws tell the proportions of the colors quite well, but hardly the size of the Bag.
+    // So the most likely Bag is searched for every size, starting with the smallest possible Bag:
+    // The best Bag with one more cube is the best one of the previous size plus a cube of some color,
+    // improved by swapping single cubes for ones of other colors, as long as that helps.
+    let mut bag = minimal_bag(games);
+
+    let minimal = bag.cubes.clone();
+    let smallest: u32 = minimal.values().sum();
+    if minimal.is_empty() || smallest > limit { return None; }
+
+    let colors: Vec<String> = minimal.keys().cloned().collect();
+    let mut value = log_likelihood(&bag, games);
+    let mut best_by_size = vec![(bag.clone(), value)];
+
+    for _ in smallest..limit {
+        let grown = colors.iter()
+            .map(|color| {
+                let mut grown = bag.clone();
+                *grown.cubes.get_mut(color).unwrap() += 1;
+                let value = log_likelihood(&grown, games);
+                (grown, value)
+            })
+            .fold(None, |best: Option<(Bag, f64)>, next| match best {
+                Some(best) if best.1 >= next.1 => Some(best),
+                _ => Some(next),
+            })
+            .unwrap();
+        (bag, value) = grown;
+
+        while let Some(swapped) = best_swap(&bag, value, games, &minimal) {
+            (bag, value) = swapped;
+        }
+
+        best_by_size.push((bag.clone(), value));
+    }
+
+    // the smallest Bag wins ties
+    let (best, best_value) = best_by_size.iter()
+        .fold(&best_by_size[0], |best, next| if next.1 > best.1 + 1e-9 { next } else { best })
+        .clone();
+
+    let plausible: Vec<(u32, &Bag)> = best_by_size.iter().enumerate()
+        .filter(|(_, (_, value))| *value >= best_value - CONFIDENCE_DROP)
+        .map(|(i, (bag, _))| (smallest + i as u32, bag))
+        .collect();
+
+    let interval = |counts: Vec<u32>, reaches_limit: bool| Interval {
+        low: *counts.iter().min().unwrap(),
+        high: if reaches_limit { None } else { counts.iter().max().copied() },
+    };
+    let reaches_limit = plausible.last().unwrap().0 == limit;
+
+    let total = interval(plausible.iter().map(|&(total, _)| total).collect(), reaches_limit);
+    let intervals = colors.iter()
+        .map(|color| (color.clone(), interval(plausible.iter().map(|(_, bag)| bag.count(color)).collect(), reaches_limit)))
+        .collect();
+
+    Some(Estimate { bag: best, log_likelihood: best_value, total, intervals })
+}
+
+fn best_swap(bag: &Bag, value: f64, games: &[Game], minimal: &BTreeMap<String, u32>) -> Option<(Bag, f64)> {
+    // The Bag of the same size, with one cube of a color replaced by one of another color,
+    // that is more likely than the given one, the most likely one if there are several
+    let mut best: Option<(Bag, f64)> = None;
+
+    for (from, &count) in &bag.cubes {
+        if count == minimal[from] { continue; }
+
+        for to in bag.cubes.keys().filter(|&to| to != from) {
+            let mut swapped = bag.clone();
+            *swapped.cubes.get_mut(from).unwrap() -= 1;
+            *swapped.cubes.get_mut(to).unwrap() += 1;
+
+            let swapped_value = log_likelihood(&swapped, games);
+            if swapped_value > best.as_ref().map_or(value, |best| best.1) + 1e-9 {
+                best = Some((swapped, swapped_value));
+            }
+        }
+    }
+
+    best
+}
+
+fn log_binomial(n: u32, k: u32) -> f64 {
+    if k > n { return f64::NEG_INFINITY; }
+    let k = k.min(n - k);
+    (0..k).map(|i| ((n - i) as f64 / (i + 1) as f64).ln()).sum()
+}
+
+
+#[cfg(test)]
+mod likelihood {
+    use crate::{Bag, Game, Repeated};
+    use crate::inference::{estimate, log_binomial, log_likelihood, Interval};
+
+    fn games(s: &str) -> Vec<Game> {
+        crate::parse_input(s, Repeated::Reject).unwrap()
+    }
+
+    fn simulate(bag: &Bag, seed: u64, game_count: usize, round_count: usize) -> Vec<Game> {
+        // Rounds of 1 to 10 cubes drawn at random from the bag
+        let mut random = seeded::random(seed);
+
+        let cubes: Vec<&str> = bag.cubes.iter()
+            .flat_map(|(color, &count)| std::iter::repeat_n(color.as_str(), count as usize))
+            .collect();
+
+        let mut log = String::new();
+        for id in 1..=game_count {
+            let rounds: Vec<String> = (0..round_count).map(|_| {
+                let mut cubes = cubes.clone();
+                let mut round = Bag::default();
+                for _ in 0..1 + random(10) {
+                    let color = cubes.swap_remove(random(cubes.len()));
+                    *round.cubes.entry(color.to_string()).or_insert(0) += 1;
+                }
+                round.cubes.iter().map(|(color, count)| format!("{count} {color}")).collect::<Vec<_>>().join(", ")
+            }).collect();
+            log.push_str(&format!("Game {id}: {}\n", rounds.join("; ")));
+        }
+
+        games(&log)
+    }
+
+    #[test]
+    fn binomial() {
+        assert_eq!(log_binomial(5, 0), 0.0);
+        assert!((log_binomial(5, 2) - 10f64.ln()).abs() < 1e-12);
+        assert!((log_binomial(40, 39) - 40f64.ln()).abs() < 1e-12);
+        assert_eq!(log_binomial(2, 3), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn log_likelihood_rounds() {
+        let games = games("Game 1: 1 red, 1 blue; 2 red");
+        let bag = Bag::from([("red", 2), ("blue", 2)]);
+
+        // 2*2/6 and 1/6
+        assert!((log_likelihood(&bag, &games) - (4.0f64 / 36.0).ln()).abs() < 1e-12);
+        assert_eq!(log_likelihood(&Bag::from([("red", 1), ("blue", 2)]), &games), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn smallest_bag() {
+        // a single pair is certain to be drawn from a bag of just that pair
+        let best = estimate(&games("Game 1: 1 red, 1 blue"), 50).unwrap();
+        assert_eq!(best.bag, Bag::from([("red", 1), ("blue", 1)]));
+        assert_eq!(best.log_likelihood, 0.0);
+        assert_eq!(best.intervals["red"].low, 1);
+        assert_eq!(best.total.low, 2);
+
+        assert_eq!(estimate(&[], 50), None);
+        assert_eq!(estimate(&games("Game 1: 60 red"), 50), None);
+    }
+
+    #[test]
+    fn simulated() {
+        // The size of the Bag is hard to tell from small draws, the proportions of the colors are not
+        let truth = Bag::from([("red", 12), ("green", 13), ("blue", 14)]);
+
+        for seed in [2, 3, 2023] {
+            let games = simulate(&truth, seed, 100, 3);
+            let best = estimate(&games, 300).unwrap();
+            assert!(best.log_likelihood >= log_likelihood(&truth, &games));
+
+            let contains = |Interval { low, high }: Interval, count: u32| low <= count && high.is_none_or(|high| count <= high);
+            assert!(contains(best.total, 39), "39 not in {:?}", best.total);
+
+            let total: u32 = best.bag.cubes.values().sum();
+            for (color, &count) in &truth.cubes {
+                assert!(contains(best.intervals[color], count), "{color}: {count} not in {:?}", best.intervals[color]);
+
+                let proportion = best.bag.count(color) as f64 / total as f64;
+                assert!((proportion - count as f64 / 39.0).abs() < 0.05, "{color}: {:?}", best.bag);
+            }
+        }
+    }
+}
@@ -0,0 +1,574 @@
+use std::cmp::max;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+pub mod generator;
+pub mod inference;
+
+// the colors of the puzzle, every Game's power is made of their counts
+pub static COLORS: [&str; 3] = ["red", "green", "blue"];
+
+pub fn parse_input(s: &str, repeated: Repeated) -> Result<Vec<Game>, Vec<ParseError>> {
+    // One Game per non-empty line, all malformed lines are reported
+    let mut games = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, game_as_str) in s.lines().enumerate() {
+        let game_as_str = game_as_str.trim();
+        if game_as_str.is_empty() { continue; }
+
+        match Game::parse_line(i + 1, game_as_str, repeated) {
+            Ok(game) => games.push(game),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if errors.is_empty() { Ok(games) } else { Err(errors) }
+}
+
+pub fn minimal_bag<'a>(games: impl IntoIterator<Item=&'a Game>) -> Bag {
+    // the smallest Bag all the Games are possible with
+    let mut bag = Bag::default();
+
+    for round in games.into_iter().flat_map(|game| &game.rounds) {
+        for (color, &count) in &round.cubes {
+            let cubes = bag.cubes.entry(color.clone()).or_insert(0);
+            *cubes = max(*cubes, count);
+        }
+    }
+
+    bag
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Repeated {
+    // What to do when a color is drawn more than once in the same Round, as in "2 red, 3 red"
+    #[default]
+    Reject,
+    // count all cubes drawn: 5 red
+    Sum,
+    // count the largest draw only: 3 red
+    Max,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    MalformedGame { line: usize, content: String },
+    MalformedCubes { line: usize, content: String },
+    InvalidCount { line: usize, color: String, count: String },
+    Overflow { line: usize, color: String },
+    RepeatedColor { line: usize, round: usize, color: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MalformedGame { line, content } =>
+                write!(f, "line {line}: expected 'Game <id>: <rounds>', got '{content}'"),
+            ParseError::MalformedCubes { line, content } =>
+                write!(f, "line {line}: expected '<count> <color>', got '{content}'"),
+            ParseError::InvalidCount { line, color, count } =>
+                write!(f, "line {line}: invalid count '{count}' of {color} cubes, expected a positive number"),
+            ParseError::Overflow { line, color } =>
+                write!(f, "line {line}: the {color} cubes drawn add up to more than {}", u32::MAX),
+            ParseError::RepeatedColor { line, round, color } =>
+                write!(f, "line {line}: {color} is drawn more than once in round {round}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bag {
+    // number of cubes of every color, colors not in the Bag have none
+    pub cubes: BTreeMap<String, u32>,
+}
+
+impl<const N: usize> From<[(&str, u32); N]> for Bag {
+    fn from(cubes: [(&str, u32); N]) -> Self {
+        Self { cubes: cubes.into_iter().map(|(color, count)| (color.to_string(), count)).collect() }
+    }
+}
+
+impl FromStr for Bag {
+    type Err = ParseError;
+
+    fn from_str(bag_as_str: &str) -> Result<Self, Self::Err> {
+        // the same as a Round: "12 red, 13 green, 14 blue",
+        // except that a Bag may hold 0 cubes of a color, which a Round never draws
+        let round = Round::parse_line(1, 1, bag_as_str, Repeated::Reject, 0)?;
+        Ok(Self { cubes: round.cubes })
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_cubes(f, &self.cubes)
+    }
+}
+
+fn write_cubes(f: &mut fmt::Formatter, cubes: &BTreeMap<String, u32>) -> fmt::Result {
+    // 14 blue, 13 green, 12 red (colors in alphabetical order)
+    let cubes: Vec<String> = cubes.iter()
+        .map(|(color, count)| format!("{count} {color}"))
+        .collect();
+    write!(f, "{}", cubes.join(", "))
+}
+
+impl Bag {
+    pub fn count(&self, color: &str) -> u32 {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn power(&self) -> u32 {
+        self.power_of(&COLORS)
+    }
+
+    pub fn power_of(&self, colors: &[&str]) -> u32 {
+        // product of the cube counts of the given colors, a color not in the Bag counts 0
+        colors.iter().map(|color| self.count(color)).product()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Round>,
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Game 50: 9 red; 5 green, 2 blue, 10 red; 5 red, 1 green
+        let rounds: Vec<String> = self.rounds.iter().map(Round::to_string).collect();
+        write!(f, "Game {}: {}", self.id, rounds.join("; "))
+    }
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(game_as_str: &str) -> Result<Self, Self::Err> {
+        Self::parse(game_as_str, Repeated::default())
+    }
+}
+
+impl Game {
+    pub fn parse(game_as_str: &str, repeated: Repeated) -> Result<Self, ParseError> {
+        Self::parse_line(1, game_as_str, repeated)
+    }
+
+    fn parse_line(line: usize, game_as_str: &str, repeated: Repeated) -> Result<Self, ParseError> {
+        let malformed = || ParseError::MalformedGame { line, content: game_as_str.to_string() };
+
+        // parse game id
+        //        vv
+        // Game 50: 9 red; 5 green, 2 blue, 10 red; 5 red, 1 green
+        // ^^^^^^^  ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+        // |        line_rounds
+        // line_game
+        let (line_game, line_rounds) = game_as_str.split_once(": ").ok_or_else(malformed)?;
+
+        //     v
+        // Game 50
+        // ^^^^ ^^
+        // _    line_id
+        let id = match line_game.split_once(" ") {
+            Some(("Game", line_id)) => line_id.parse().map_err(|_| malformed())?,
+            _ => return Err(malformed()),
+        };
+
+        // extract rounds
+        let mut rounds: Vec<Round> = Vec::new();
+
+        //      vv                       vv
+        // 9 red; 5 green, 2 blue, 10 red; 5 red, 1 green
+        // ^^^^^  ^^^^^^^^^^^^^^^^^^^^^^^  ^^^^^^^^^^^^^^
+        // |      |                        line_round[n]  ...
+        // |      line_round[1]
+        // line_round[0]
+        for line_round in line_rounds.split("; ") {
+            rounds.push(Round::parse_line(line, rounds.len() + 1, line_round, repeated, 1)?);
+        }
+
+        Ok(Self { id, rounds })
+    }
+
+    pub fn possible(&self, bag: &Bag) -> bool {
+        // a Game with a certain Bag is possible,
+        // iff ALL Rounds are possible with this Bag
+        self.rounds.iter()
+            .all(|round| round.possible(bag))
+    }
+
+    pub fn minimal_bag(&self) -> Bag {
+        // the smallest Bag this Game is possible with:
+        // for every color drawn, as many cubes as the Round needing the most of them
+        minimal_bag([self])
+    }
+
+    pub fn tightest_failing_round(&self, bag: &Bag) -> Option<(usize, Bag)> {
+        // The Round (by index) that is furthest from being possible with the Bag,
+        // the one needing the most cubes missing in the Bag, and the cubes it misses.
+        // The first one wins ties, None if the Game is possible.
+        self.rounds.iter()
+            .map(|round| round.missing(bag))
+            .enumerate()
+            .filter(|(_, missing)| !missing.cubes.is_empty())
+            .fold(None, |tightest: Option<(usize, Bag)>, (i, missing)| match tightest {
+                Some(tightest) if tightest.1.cubes.values().sum::<u32>() >= missing.cubes.values().sum() => Some(tightest),
+                _ => Some((i, missing)),
+            })
+    }
+
+    pub fn power(&self) -> u32 {
+        self.minimal_bag().power()
+    }
+
+    pub fn power_of(&self, colors: &[&str]) -> u32 {
+        self.minimal_bag().power_of(colors)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Round {
+    // number of cubes drawn of every color, colors not drawn are missing
+    pub cubes: BTreeMap<String, u32>,
+}
+
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_cubes(f, &self.cubes)
+    }
+}
+
+impl<const N: usize> From<[(&str, u32); N]> for Round {
+    fn from(cubes: [(&str, u32); N]) -> Self {
+        Self { cubes: cubes.into_iter().map(|(color, count)| (color.to_string(), count)).collect() }
+    }
+}
+
+impl Round {
+    fn parse_line(line: usize, round: usize, line_round: &str, repeated: Repeated, least: u32) -> Result<Self, ParseError> {
+        // counts below the least one are invalid
+        let mut cubes = BTreeMap::new();
+
+        //        vv      vv
+        // 5 green, 2 blue, 10 red
+        // ^^^^^^^  ^^^^^^  ^^^^^^
+        // |        |       cubes[n] ...
+        // cubes[0] cubes[1]
+        for cube in line_round.split(", ") {
+
+            //  v
+            // 5 green
+            // ^ ^^^^^
+            // | cube_color
+            // cube_count
+            let (cube_count, cube_color) = cube.split_once(" ")
+                .ok_or_else(|| ParseError::MalformedCubes { line, content: cube.to_string() })?;
+
+            let count: u32 = match cube_count.parse() {
+                Ok(count) if count >= least => count,
+                _ => return Err(ParseError::InvalidCount {
+                    line,
+                    color: cube_color.to_string(),
+                    count: cube_count.to_string(),
+                }),
+            };
+
+            match cubes.entry(cube_color.to_string()) {
+                Entry::Vacant(entry) => { entry.insert(count); }
+                Entry::Occupied(mut entry) => match repeated {
+                    Repeated::Reject => return Err(ParseError::RepeatedColor { line, round, color: cube_color.to_string() }),
+                    Repeated::Sum => *entry.get_mut() = entry.get().checked_add(count)
+                        .ok_or_else(|| ParseError::Overflow { line, color: cube_color.to_string() })?,
+                    Repeated::Max => *entry.get_mut() = max(*entry.get(), count),
+                },
+            }
+        }
+
+        Ok(Self { cubes })
+    }
+
+    pub fn count(&self, color: &str) -> u32 {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn possible(&self, bag: &Bag) -> bool {
+        // a Round is possible,
+        // iff the Round needs less or equal the amount of cubes in the Bag, for every color
+        self.cubes.iter()
+            .all(|(color, &count)| count <= bag.count(color))
+    }
+
+    pub fn missing(&self, bag: &Bag) -> Bag {
+        // the cubes drawn in this Round, that are not in the Bag
+        let cubes = self.cubes.iter()
+            .filter(|&(color, &count)| count > bag.count(color))
+            .map(|(color, &count)| (color.clone(), count - bag.count(color)))
+            .collect();
+        Bag { cubes }
+    }
+}
+
+
+#[cfg(test)]
+mod cube_game {
+    use std::fs::read_to_string;
+
+    use crate::{parse_input, Bag, Game, ParseError, Repeated, Round};
+
+    fn example() -> Vec<Game> {
+        parse_input(&read_to_string("src/example").unwrap(), Repeated::Reject).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let game = "Game 50: 9 red; 5 green, 2 blue, 10 red; 5 red, 1 green".parse::<Game>().unwrap();
+
+        assert_eq!(game.id, 50);
+        assert_eq!(game.rounds, vec![
+            Round::from([("red", 9)]),
+            Round::from([("green", 5), ("blue", 2), ("red", 10)]),
+            Round::from([("red", 5), ("green", 1)]),
+        ]);
+        assert_eq!(game.rounds[1].count("green"), 5);
+        assert_eq!(game.rounds[0].count("green"), 0);
+    }
+
+    #[test]
+    fn print() {
+        // colors are printed in alphabetical order, which makes the text canonical
+        let game = "Game 50: 9 red; 5 green, 2 blue, 10 red; 5 red, 1 green".parse::<Game>().unwrap();
+        assert_eq!(game.to_string(), "Game 50: 9 red; 2 blue, 5 green, 10 red; 1 green, 5 red");
+        assert_eq!(game.rounds[2].to_string(), "1 green, 5 red");
+
+        for game in example() {
+            assert_eq!(game.to_string().parse::<Game>().unwrap(), game);
+        }
+    }
+
+    #[test]
+    fn possible() {
+        // part 1
+        let bag = Bag::from([("red", 12), ("green", 13), ("blue", 14)]);
+
+        let possible: Vec<u32> = example().iter()
+            .filter(|game| game.possible(&bag))
+            .map(|game| game.id)
+            .collect();
+        assert_eq!(possible, vec![1, 2, 5]);
+        assert_eq!(possible.iter().sum::<u32>(), 8);
+    }
+
+    #[test]
+    fn minimal_bag() {
+        // part 2
+        let games = example();
+
+        assert_eq!(games[0].minimal_bag(), Bag::from([("red", 4), ("green", 2), ("blue", 6)]));
+        assert_eq!(games.iter().map(Game::power).collect::<Vec<_>>(), vec![48, 12, 1560, 630, 36]);
+        assert_eq!(games.iter().map(Game::power).sum::<u32>(), 2286);
+
+        for game in &games {
+            assert!(game.possible(&game.minimal_bag()));
+        }
+    }
+
+    #[test]
+    fn power_without_blue() {
+        // a Game never drawing blue could do without any blue cubes
+        let game = "Game 1: 3 red, 2 green; 4 red".parse::<Game>().unwrap();
+
+        assert_eq!(game.minimal_bag().count("blue"), 0);
+        assert_eq!(game.power(), 0);
+        assert_eq!(game.power_of(&["red", "green"]), 4 * 2);
+        assert_eq!(Bag::default().power(), 0);
+    }
+
+    #[test]
+    fn colors() {
+        let game = "Game 7: 3 purple, 1 red; 2 purple, 4 yellow; 2 red".parse::<Game>().unwrap();
+
+        assert_eq!(game.rounds[1], Round::from([("purple", 2), ("yellow", 4)]));
+        assert_eq!(game.minimal_bag(), Bag::from([("purple", 3), ("red", 2), ("yellow", 4)]));
+        assert_eq!(game.power_of(&["purple", "red", "yellow"]), 3 * 2 * 4);
+        // neither green nor blue cubes are ever drawn
+        assert_eq!(game.power(), 0);
+
+        // there are no purple or yellow cubes in the Bag of part 1
+        assert!(!game.possible(&Bag::from([("red", 12), ("green", 13), ("blue", 14)])));
+        assert!(game.possible(&Bag::from([("red", 2), ("purple", 3), ("yellow", 9), ("green", 1)])));
+        assert!(!game.possible(&Bag::from([("red", 2), ("purple", 2), ("yellow", 9)])));
+    }
+
+    #[test]
+    fn repeated() {
+        let game_as_str = "Game 3: 2 red, 1 blue, 3 red; 4 red";
+
+        assert_eq!(Game::parse(game_as_str, Repeated::Reject).unwrap_err(),
+                   ParseError::RepeatedColor { line: 1, round: 1, color: "red".to_string() });
+        assert_eq!(game_as_str.parse::<Game>().unwrap_err(),
+                   ParseError::RepeatedColor { line: 1, round: 1, color: "red".to_string() });
+
+        let game = Game::parse(game_as_str, Repeated::Sum).unwrap();
+        assert_eq!(game.rounds, vec![Round::from([("red", 5), ("blue", 1)]), Round::from([("red", 4)])]);
+        assert_eq!(game.minimal_bag(), Bag::from([("red", 5), ("blue", 1)]));
+
+        let game = Game::parse(game_as_str, Repeated::Max).unwrap();
+        assert_eq!(game.rounds, vec![Round::from([("red", 3), ("blue", 1)]), Round::from([("red", 4)])]);
+        assert_eq!(game.minimal_bag(), Bag::from([("red", 4), ("blue", 1)]));
+
+        // the same color in different rounds is fine in every mode
+        assert!(Game::parse("Game 1: 1 red; 1 red", Repeated::Reject).is_ok());
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(Game::parse("Game 1: 0 red", Repeated::Reject).unwrap_err(),
+                   ParseError::InvalidCount { line: 1, color: "red".to_string(), count: "0".to_string() });
+        assert_eq!(Game::parse("Game 1: 1 blue; -3 red", Repeated::Reject).unwrap_err(),
+                   ParseError::InvalidCount { line: 1, color: "red".to_string(), count: "-3".to_string() });
+        assert_eq!(Game::parse("Game 1: 4294967295 red, 1 red", Repeated::Sum).unwrap_err(),
+                   ParseError::Overflow { line: 1, color: "red".to_string() });
+        assert_eq!(ParseError::Overflow { line: 1, color: "red".to_string() }.to_string(),
+                   "line 1: the red cubes drawn add up to more than 4294967295");
+
+        assert_eq!(parse_input("Game 1: 1 red\n\nGame 2 1 red\nGame x: 1 red\nGame 4: 1red; 2 blue\nGame 5: 2 red, many blue", Repeated::Reject).unwrap_err(), vec![
+            ParseError::MalformedGame { line: 3, content: "Game 2 1 red".to_string() },
+            ParseError::MalformedGame { line: 4, content: "Game x: 1 red".to_string() },
+            ParseError::MalformedCubes { line: 5, content: "1red".to_string() },
+            ParseError::InvalidCount { line: 6, color: "blue".to_string(), count: "many".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn what_if() {
+        let games = example();
+        let bag: Bag = "12 red, 13 green, 14 blue".parse().unwrap();
+        assert_eq!(bag, Bag::from([("red", 12), ("green", 13), ("blue", 14)]));
+        assert_eq!(bag.to_string(), "14 blue, 13 green, 12 red");
+        assert!("12 red, 13 red".parse::<Bag>().is_err());
+        // a Bag may hold 0 cubes of a color, a Round never draws 0
+        assert_eq!("0 red, 13 green".parse::<Bag>(), Ok(Bag::from([("red", 0), ("green", 13)])));
+        assert!("-1 red".parse::<Bag>().is_err());
+
+        assert_eq!(games[0].tightest_failing_round(&bag), None);
+        // 8 green, 6 blue, 20 red
+        assert_eq!(games[2].tightest_failing_round(&bag), Some((0, Bag::from([("red", 8)]))));
+        // 3 green, 15 blue, 14 red
+        assert_eq!(games[3].tightest_failing_round(&bag), Some((2, Bag::from([("red", 2), ("blue", 1)]))));
+        assert_eq!(games[3].rounds[2].missing(&Bag::from([("red", 20)])), Bag::from([("green", 3), ("blue", 15)]));
+
+        assert_eq!(crate::minimal_bag(&games[2..4]), Bag::from([("red", 20), ("green", 13), ("blue", 15)]));
+        assert_eq!(crate::minimal_bag(&games), crate::minimal_bag([&games[2], &games[3]]));
+        assert_eq!(crate::minimal_bag(&[]), Bag::default());
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use std::collections::BTreeMap;
+
+    use proptest::prelude::*;
+
+    use crate::{minimal_bag, parse_input, Bag, Game, Repeated};
+
+    fn cubes() -> impl Strategy<Value=BTreeMap<String, u32>> {
+        let color = prop_oneof!["red", "green", "blue", "yellow"].prop_map(String::from);
+        prop::collection::btree_map(color, 1..30u32, 1..4)
+    }
+
+    fn games() -> impl Strategy<Value=Vec<(u32, Vec<BTreeMap<String, u32>>)>> {
+        prop::collection::vec(prop::collection::vec(cubes(), 1..6), 1..10)
+            .prop_map(|games| (1..).zip(games).collect())
+    }
+
+    fn text(games: &[(u32, Vec<BTreeMap<String, u32>>)]) -> String {
+        // Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+        games.iter()
+            .map(|(id, rounds)| {
+                let rounds: Vec<String> = rounds.iter()
+                    .map(|cubes| Bag { cubes: cubes.clone() }.to_string())
+                    .collect();
+                format!("Game {id}: {}\n", rounds.join("; "))
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn parse_round_trip(generated in games()) {
+            let games = parse_input(&text(&generated), Repeated::Reject).unwrap();
+
+            prop_assert_eq!(games.len(), generated.len());
+            for (game, (id, rounds)) in games.iter().zip(&generated) {
+                prop_assert_eq!(game.id, *id);
+                let parsed: Vec<&BTreeMap<String, u32>> = game.rounds.iter().map(|round| &round.cubes).collect();
+                prop_assert_eq!(parsed, rounds.iter().collect::<Vec<_>>());
+            }
+
+            // and printed back to the very same text
+            let printed: String = games.iter().map(|game| game.to_string() + "\n").collect();
+            prop_assert_eq!(printed, text(&generated));
+        }
+
+        #[test]
+        fn bag_round_trip(cubes in cubes()) {
+            let bag = Bag { cubes };
+            prop_assert_eq!(bag.to_string().parse::<Bag>().unwrap(), bag);
+        }
+
+        #[test]
+        fn minimal_bag_makes_the_game_possible(generated in games()) {
+            for game in parse_input(&text(&generated), Repeated::Reject).unwrap() {
+                let bag = game.minimal_bag();
+                prop_assert!(game.possible(&bag));
+                prop_assert_eq!(game.tightest_failing_round(&bag), None);
+
+                // and no cube can be left out of it
+                for color in bag.cubes.keys() {
+                    let mut smaller = bag.clone();
+                    *smaller.cubes.get_mut(color).unwrap() -= 1;
+                    prop_assert!(!game.possible(&smaller), "{} without one {}", bag, color);
+                }
+            }
+        }
+
+        #[test]
+        fn minimal_bag_of_all_games(generated in games()) {
+            let games = parse_input(&text(&generated), Repeated::Reject).unwrap();
+            let bag = minimal_bag(&games);
+
+            prop_assert!(games.iter().all(|game| game.possible(&bag)));
+            for game in &games {
+                prop_assert!(game.minimal_bag().cubes.iter().all(|(color, &count)| count <= bag.count(color)));
+            }
+        }
+
+        #[test]
+        fn larger_bags_stay_possible(generated in games(), bag in cubes(), extra in cubes()) {
+            let bag = Bag { cubes: bag };
+            let mut larger = bag.clone();
+            for (color, count) in extra {
+                *larger.cubes.entry(color).or_insert(0) += count;
+            }
+
+            for game in parse_input(&text(&generated), Repeated::Reject).unwrap() {
+                if game.possible(&bag) { prop_assert!(game.possible(&larger)); }
+                prop_assert_eq!(game.possible(&bag), game.tightest_failing_round(&bag).is_none());
+            }
+        }
+
+        #[test]
+        fn repeated_colors_sum_up(count in 1..1000u32, split in 1..1000u32) {
+            let split = split % count;
+            prop_assume!(split > 0);
+            let game: Game = Game::parse(&format!("Game 1: {split} red, {} red", count - split), Repeated::Sum).unwrap();
+            prop_assert_eq!(game.rounds[0].count("red"), count);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::env::args;
+use std::fs::read_to_string;
+
+use aoc02::{minimal_bag, parse_input, Bag, Game, Repeated};
+use aoc02::generator::generate;
+use aoc02::inference::{estimate, Interval};
+use seeded::generate_command;
+
+fn main() {
+    // Usage: aoc02 infer <input> [<most cubes in the bag>]
+    //        aoc02 whatif <input> "12 red, 13 green, 14 blue" [<game id>...]
+    //        aoc02 generate <games> [<seed>] > <input>    (the answers go to stderr)
+    let usage = "usage: aoc02 infer <input> [<most cubes in the bag>] | aoc02 whatif <input> <bag> [<game id>...] \
+                 | aoc02 generate <games> [<seed>]";
+    let args: Vec<String> = args().collect();
+
+    match args.get(1).map(String::as_str) {
+        Some("infer") => {
+            let games = read_games(args.get(2).expect(usage));
+            let limit = args.get(3).map_or(200, |limit| limit.parse().expect("limit must be a number"));
+            infer(&games, limit);
+        }
+        Some("whatif") => {
+            let games = read_games(args.get(2).expect(usage));
+            let bag: Bag = match args.get(3).expect(usage).parse() {
+                Ok(bag) => bag,
+                Err(error) => {
+                    eprintln!("bag: {}", error);
+                    std::process::exit(1);
+                }
+            };
+            let ids: Vec<u32> = args[4..].iter()
+                .map(|id| id.parse().expect("game ids must be numbers"))
+                .collect();
+            what_if(&games, &bag, &ids);
+        }
+        Some("generate") => {
+            generate_command(&args[1..], usage, 1, |sizes, seed, writer| {
+                generate(writer, seed, sizes[0].parse().expect("games must be a number"))
+            });
+        }
+        _ => {
+            eprintln!("{}", usage);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn read_games(path: &str) -> Vec<Game> {
+    match parse_input(&read_to_string(path).unwrap(), Repeated::Reject) {
+        Ok(games) => games,
+        Err(errors) => {
+            for error in errors { eprintln!("{}", error); }
+            std::process::exit(1);
+        }
+    }
+}
+
+fn infer(games: &[Game], limit: u32) {
+    let Some(estimate) = estimate(games, limit) else {
+        println!("no bag of at most {limit} cubes can explain the games");
+        return;
+    };
+
+    let interval = |Interval { low, high }: Interval| match high {
+        Some(high) => format!("{low}..={high}"),
+        None => format!("{low} or more"),
+    };
+
+    println!("most likely bag (log-likelihood {:.2}):", estimate.log_likelihood);
+    for (color, count) in &estimate.bag.cubes {
+        println!("    {color}: {count} (plausible: {})", interval(estimate.intervals[color]));
+    }
+    println!("cubes in the bag: {} (plausible: {})",
+             estimate.bag.cubes.values().sum::<u32>(), interval(estimate.total));
+}
+
+fn what_if(games: &[Game], bag: &Bag, ids: &[u32]) {
+    let possible: Vec<u32> = games.iter()
+        .filter(|game| game.possible(bag))
+        .map(|game| game.id)
+        .collect();
+    let possible_ids: Vec<String> = possible.iter().map(u32::to_string).collect();
+    println!("possible with {}: {} games, id sum {}", bag, possible.len(), possible.iter().sum::<u32>());
+    println!("    {}", possible_ids.join(", "));
+
+    for game in games {
+        if let Some((round, missing)) = game.tightest_failing_round(bag) {
+            println!("game {} is impossible, round {} is short of {}", game.id, round + 1, missing);
+        }
+    }
+
+    if ids.is_empty() { return; }
+
+    let selected: Vec<&Game> = ids.iter()
+        .filter_map(|&id| {
+            let game = games.iter().find(|game| game.id == id);
+            if game.is_none() { eprintln!("there is no game {}", id); }
+            game
+        })
+        .collect();
+    let ids: Vec<String> = selected.iter().map(|game| game.id.to_string()).collect();
+    println!("smallest bag for games {}: {}", ids.join(", "), minimal_bag(selected));
+}
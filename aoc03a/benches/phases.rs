@@ -0,0 +1,27 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc03a::{part_number_sum, Engine};
+use aoc03a::generator::generate;
+
+fn phases(c: &mut Criterion) {
+    let mut schematic = Vec::new();
+    generate(&mut schematic, 0x2023_1203, 1000, 1000).unwrap();
+    let schematic = String::from_utf8(schematic).unwrap();
+
+    let mut group = c.benchmark_group("parse");
+    group.sample_size(10);
+    group.bench_function("aoc03a 1000x1000 fields", |b| b.iter(|| Engine::from(black_box(&schematic).clone())));
+    group.finish();
+
+    let engine = Engine::from(schematic);
+
+    let mut group = c.benchmark_group("solve");
+    group.sample_size(10);
+    group.bench_function("aoc03a 1000x1000 fields", |b| b.iter(|| part_number_sum(black_box(&engine))));
+    group.finish();
+}
+
+criterion_group!(benches, phases);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+use std::io::{self, Write};
+
+static SYMBOLS: &[u8] = b"*#+$/@=%-&";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    // sum of all part numbers
+    pub part1: u64,
+}
+
+impl seeded::Answers for Answers {
+    fn parts(&self) -> Vec<u128> { vec![self.part1.into()] }
+}
+
+pub fn generate<W: Write>(mut writer: W, seed: u64, rows: usize, columns: usize) -> io::Result<Answers> {
+    // A schematic like the puzzle input: mostly empty fields,
+    // numbers of 1 to 3 digits, each followed by an empty field or a symbol, and a few more symbols.
+    // The answer comes from the numbers as they are placed, not from reading the schematic.
+    let mut random = seeded::random(seed);
+
+    let mut fields = vec![vec![b'.'; columns]; rows];
+    // (row, first column, column after the last digit, value)
+    let mut numbers: Vec<(usize, usize, usize, u64)> = Vec::new();
+
+    for (r, row) in fields.iter_mut().enumerate() {
+        let mut p = 0;
+        while p < columns {
+            match random(20) {
+                0..=2 => {
+                    let digits = (1 + random(3)).min(columns - p);
+                    let value = 10u64.pow(digits as u32 - 1) + random(9 * 10usize.pow(digits as u32 - 1)) as u64;
+                    row[p..p + digits].copy_from_slice(value.to_string().as_bytes());
+                    numbers.push((r, p, p + digits, value));
+                    // the field after a number is never a digit
+                    p += digits + 1;
+                }
+                3 => {
+                    row[p] = SYMBOLS[random(SYMBOLS.len())];
+                    p += 1;
+                }
+                _ => p += 1,
+            }
+        }
+    }
+
+    let is_symbol = |c: u8| c != b'.' && !c.is_ascii_digit();
+    let mut answers = Answers::default();
+    for (r, from, to, value) in numbers {
+        let part = fields[r.saturating_sub(1)..rows.min(r + 2)].iter()
+            .any(|row| row[from.saturating_sub(1)..columns.min(to + 1)].iter().any(|&c| is_symbol(c)));
+        if part { answers.part1 += value; }
+    }
+
+    for row in &fields {
+        writer.write_all(row)?;
+        writer.write_all(b"\n")?;
+    }
+
+    writer.flush()?;
+    Ok(answers)
+}
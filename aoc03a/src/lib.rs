@@ -0,0 +1,295 @@
+use std::cmp::max;
+use std::collections::HashSet;
+use std::fmt;
+use std::num::ParseIntError;
+
+pub mod generator;
+
+pub fn part_number_sum(engine: &Engine) -> u64 {
+    engine.get_numbers().iter()
+        .filter(|n| engine.is_part_number(n))
+        .map(Number::to_digit).map(Result::unwrap)
+        .map(u64::from)
+        .sum()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Engine {
+    fields: Vec<Vec<char>>,
+    width: usize,
+    height: usize,
+    symbols: HashSet<char>,
+}
+
+impl fmt::Display for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // the schematic as read, one row per line
+        let rows: Vec<String> = self.fields.iter().map(|row| row.iter().collect()).collect();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
+impl Engine {
+    fn new() -> Engine {
+        let width = 0;
+        let height = 0;
+        Self {
+            fields: vec![vec!['.'; width]; height],
+            width,
+            height,
+            symbols: HashSet::new(),
+        }
+    }
+
+    pub fn from(s: String) -> Self {
+        let mut engine = Engine::new();
+        for line in s.lines() { engine.add_row(line) }
+        engine
+    }
+
+    fn add_row(&mut self, row_string: &str) {
+        let mut row: Vec<char> = Vec::new();
+
+        for c in row_string.chars() {
+            row.push(c);
+            if !c.is_numeric() && c != '.' { self.symbols.insert(c); }
+        }
+
+        // Assumption: Every line has the same length
+        self.width = max(self.width, row.len());
+        self.height += 1;
+        self.fields.push(row);
+    }
+
+
+    fn get_neighbors(&self, row: usize, pos: usize) -> Vec<char> {
+        let mut neighbors: Vec<char> = Vec::new();
+
+        let neighbor_window: [(isize, isize); 8] = [
+            (-1, -1), (-1, 0), (-1, 1),
+            (0, -1), /*(0,0)*/ (0, 1),
+            (1, -1), (1, 0), (1, 1),
+        ];
+
+        for (window_row, window_pos) in neighbor_window {
+            let neighbor_row = row as isize + window_row;
+            let neighbor_pos = pos as isize + window_pos;
+
+            if neighbor_row >= 0
+                && neighbor_pos >= 0
+                && neighbor_row < self.height as isize
+                && neighbor_pos < self.width as isize
+            {
+                let n = self.fields[neighbor_row as usize][neighbor_pos as usize];
+                if n != '.' { neighbors.push(n); }
+            }
+        }
+
+        neighbors
+    }
+
+    fn get_numbers(&self) -> Vec<Number> {
+        // Extracts all Numbers (as defined) from the Engine.
+        // Numbers are build one char at a time:
+        // From left to right, upper to lower check every char if it is a digit.
+        // If it is a digit
+        // - append the char to the Number currently build
+        // - append the neighbouring chars of this field to the list of neighbours
+        // If the current character is not a digit, this might be the end of a Number being build.
+        // The number can be added to the list of numbers.
+        // The same goes for the end of a row, Numbers do not continue on the next row.
+
+        let mut numbers: Vec<Number> = Vec::new();
+
+        // collectors for the build up of the Number
+        let mut chars: Vec<char> = Vec::new();
+        let mut neighbors: Vec<char> = Vec::new();
+
+        for r in 0..self.height {
+            for p in 0..self.width {
+                let c = self.fields[r][p];
+
+                if c.is_ascii_digit() {
+                    chars.push(c);
+                    neighbors.append(&mut self.get_neighbors(r, p));
+                }
+
+                // a Number ends before the first char that is not a digit, or with its row
+                let ends = !c.is_ascii_digit() || p + 1 == self.width;
+                if ends && !chars.is_empty() {
+                    // create a new Number from the collected characters
+                    numbers.push(Number { chars: chars.clone(), neighbors: neighbors.clone() });
+
+                    // reset the collectors
+                    chars.clear();
+                    neighbors.clear();
+                }
+            }
+        }
+        numbers
+    }
+
+    fn is_part_number(&self, number: &Number) -> bool {
+        // a number is a part number iff any of its neighboring chars is a symbol
+        number.neighbors.iter()
+            .any(|x| self.symbols.contains(x))
+    }
+}
+
+struct Number {
+    chars: Vec<char>,
+    neighbors: Vec<char>,
+}
+
+impl Number {
+    fn to_digit(&self) -> Result<u32, ParseIntError> {
+        let mut number_string = String::new();
+        for c in self.chars.clone().into_iter() { number_string.push(c); }
+        number_string.parse()
+    }
+}
+
+
+#[cfg(test)]
+mod numbers {
+    use std::fs::read_to_string;
+
+    use crate::{part_number_sum, Engine};
+
+    fn numbers(engine: &Engine) -> Vec<String> {
+        engine.get_numbers().iter().map(|number| number.chars.iter().collect()).collect()
+    }
+
+    #[test]
+    fn example() {
+        let engine = Engine::from(read_to_string("src/example").unwrap());
+
+        assert_eq!(numbers(&engine), vec!["467", "114", "35", "633", "617", "58", "592", "755", "664", "598"]);
+        assert_eq!(part_number_sum(&engine), 4361);
+    }
+
+    #[test]
+    fn numbers_end_with_their_row() {
+        // the 1 ends its row and is no part number, the 2 starting the next row is
+        let engine = Engine::from(".......1\n2*......\n".to_string());
+
+        assert_eq!(numbers(&engine), vec!["1", "2"]);
+        assert_eq!(part_number_sum(&engine), 2);
+    }
+}
+
+
+#[cfg(test)]
+mod synthetic {
+    use crate::{part_number_sum, Engine};
+    use crate::generator::generate;
+
+    #[test]
+    fn generate_answers() {
+        let mut schematic = Vec::new();
+        let answers = generate(&mut schematic, 42, 200, 140).unwrap();
+
+        let schematic = String::from_utf8(schematic).unwrap();
+        // numbers in the last column, which must not run on into the next row
+        assert!(schematic.lines().any(|row| row.ends_with(|c: char| c.is_ascii_digit())));
+
+        let engine = Engine::from(schematic);
+        assert_eq!((engine.height, engine.width), (200, 140));
+        assert_eq!(part_number_sum(&engine), answers.part1);
+    }
+}
+
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use crate::Engine;
+
+    fn schematic() -> impl Strategy<Value=Vec<Vec<char>>> {
+        // mostly empty fields, some digits and a few symbols
+        let field = prop_oneof![
+            6 => Just('.'),
+            3 => prop::char::range('0', '9'),
+            1 => prop::sample::select(vec!['*', '#', '+', '$', '/', '@', '=', '%', '-', '&']),
+        ];
+
+        // rows short enough for every number and their sum to fit a u32
+        (1..10usize, 1..8usize)
+            .prop_flat_map(move |(height, width)|
+                prop::collection::vec(prop::collection::vec(field.clone(), width), height))
+    }
+
+    fn text(rows: &[Vec<char>]) -> String {
+        rows.iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+    }
+
+    fn part_number_sum(rows: &[Vec<char>]) -> u32 {
+        // Every maximal run of digits in a row is a number,
+        // it is a part number if a symbol is in the box around it
+        let is_symbol = |c: char| c != '.' && !c.is_ascii_digit();
+        let mut sum = 0;
+
+        for (r, row) in rows.iter().enumerate() {
+            let mut p = 0;
+            while p < row.len() {
+                if !row[p].is_ascii_digit() { p += 1; continue; }
+
+                let start = p;
+                while p < row.len() && row[p].is_ascii_digit() { p += 1; }
+
+                let boxed = rows[r.saturating_sub(1)..rows.len().min(r + 2)].iter()
+                    .any(|row| row[start.saturating_sub(1)..row.len().min(p + 1)].iter().any(|&c| is_symbol(c)));
+                if boxed {
+                    sum += row[start..p].iter().collect::<String>().parse::<u32>().unwrap();
+                }
+            }
+        }
+
+        sum
+    }
+
+    #[test]
+    fn print_example() {
+        let example = std::fs::read_to_string("src/example").unwrap();
+        let engine = Engine::from(example.clone());
+
+        assert_eq!(engine.to_string(), example.trim_end());
+        assert_eq!(Engine::from(engine.to_string()), engine);
+    }
+
+    proptest! {
+        #[test]
+        fn parse_round_trip(rows in schematic()) {
+            let text = text(&rows);
+            let engine = Engine::from(text.clone());
+
+            prop_assert_eq!(&engine.fields, &rows);
+            prop_assert_eq!((engine.height, engine.width), (rows.len(), rows[0].len()));
+
+            prop_assert_eq!(engine.to_string(), text.trim_end());
+            prop_assert_eq!(Engine::from(engine.to_string()), engine);
+        }
+
+        #[test]
+        fn numbers_are_runs_of_digits(rows in schematic()) {
+            let engine = Engine::from(text(&rows));
+            let numbers: String = engine.get_numbers().iter()
+                .map(|number| number.chars.iter().collect::<String>() + " ")
+                .collect();
+
+            let runs: String = rows.iter()
+                .flat_map(|row| row.split(|c| !c.is_ascii_digit()))
+                .filter(|run| !run.is_empty())
+                .map(|run| run.iter().collect::<String>() + " ")
+                .collect();
+            prop_assert_eq!(numbers, runs);
+        }
+
+        #[test]
+        fn part_numbers(rows in schematic()) {
+            let engine = Engine::from(text(&rows));
+            prop_assert_eq!(crate::part_number_sum(&engine), part_number_sum(&rows) as u64);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc04::copies_cascade;
+use aoc04::generator::generate;
+use aoc04::validation::parse_cards;
+
+fn phases(c: &mut Criterion) {
+    let mut pile = Vec::new();
+    generate(&mut pile, 0x2023_1204, 100_000).unwrap();
+    let pile = String::from_utf8(pile).unwrap();
+
+    let mut group = c.benchmark_group("parse");
+    group.sample_size(10);
+    group.bench_function("aoc04 100000 cards", |b| b.iter(|| parse_cards(black_box(&pile))));
+    group.finish();
+
+    let (cards, _) = parse_cards(&pile);
+
+    let mut group = c.benchmark_group("solve");
+    group.bench_function("aoc04a 100000 cards", |b| b.iter(|| {
+        black_box(&cards).iter().map(|card| card.points()).sum::<usize>()
+    }));
+    group.bench_function("aoc04b 100000 cards", |b| b.iter(|| copies_cascade(black_box(&cards)).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, phases);
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc04::Card;
+
+fn generate(seed: u64, cards: usize) -> String {
+    // Cards laid out like the puzzle input: 10 winning numbers and 25 numbers you have,
+    // all different numbers from 1 to 99 on each side.
+    let mut random = seeded::random(seed);
+
+    let mut side = |count: usize| {
+        let mut numbers: Vec<usize> = (1..=99).collect();
+        (0..count)
+            .map(|_| format!("{:>2}", numbers.swap_remove(random(numbers.len()))))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut s = String::new();
+    for id in 1..=cards {
+        s.push_str(&format!("Card {id:>7}: {} | {}\n", side(10), side(25)));
+    }
+    s
+}
+
+// The previous approach: two hash sets per card, intersected
+fn hash_set_matches(s: &str) -> usize {
+    let (_, numbers) = s.split_once(": ").unwrap();
+    let (winning, you_have) = numbers.split_once(" | ").unwrap();
+
+    let winning: HashSet<u32> = winning.split_whitespace().map(|n| n.parse().unwrap()).collect();
+    let you_have: HashSet<u32> = you_have.split_whitespace().map(|n| n.parse().unwrap()).collect();
+    winning.intersection(&you_have).count()
+}
+
+fn scratchcards(c: &mut Criterion) {
+    let input = generate(0x2023_1204, 1_000_000);
+    assert_eq!(input.lines().map(hash_set_matches).sum::<usize>(),
+               input.lines().map(|card| Card::from(card).matches()).sum::<usize>());
+
+    let mut group = c.benchmark_group("matches of 1000000 cards");
+    group.sample_size(10);
+
+    group.bench_function("hash sets", |b| b.iter(|| {
+        black_box(&input).lines()
+            .map(hash_set_matches)
+            .sum::<usize>()
+    }));
+
+    group.bench_function("bitsets", |b| b.iter(|| {
+        black_box(&input).lines()
+            .map(|card| Card::from(card).matches())
+            .sum::<usize>()
+    }));
+
+    group.finish();
+}
+
+criterion_group!(benches, scratchcards);
+criterion_main!(benches);
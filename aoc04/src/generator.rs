@@ -0,0 +1,91 @@
+use std::io::{self, Write};
+
+// numbers on each side of a card, as in the puzzle input
+const WINNING: usize = 10;
+const YOU_HAVE: usize = 25;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    // sum of the points of all cards
+    pub part1: u64,
+    // number of cards, copies included, summed as u128 like aoc04b does
+    pub part2: u128,
+}
+
+impl seeded::Answers for Answers {
+    fn parts(&self) -> Vec<u128> { vec![self.part1.into(), self.part2] }
+}
+
+pub fn generate<W: Write>(mut writer: W, seed: u64, cards: usize) -> io::Result<Answers> {
+    // Cards laid out like the puzzle input: 10 winning numbers and 25 numbers you have,
+    // all different numbers from 1 to 99 on each side.
+    //
+    // Random numbers would match about 2.5 times per card, making the copies grow exponentially.
+    // So the matches are chosen first, as in the puzzle input most cards match none,
+    // and the numbers you have are made to match exactly that often.
+    let mut random = seeded::random(seed);
+
+    let width = cards.to_string().len().max(3);
+    let column = |numbers: &[u32]| numbers.iter().map(|number| format!("{number:>2}")).collect::<Vec<_>>().join(" ");
+
+    let mut matches = Vec::with_capacity(cards);
+    for id in 1..=cards {
+        let matching = if random(10) < 7 { 0 } else { 1 + random(4) };
+
+        let mut numbers: Vec<u32> = (1..=99).collect();
+        for i in 0..numbers.len() {
+            let j = i + random(numbers.len() - i);
+            numbers.swap(i, j);
+        }
+        let winning = &numbers[..WINNING];
+        let mut you_have: Vec<u32> = winning[..matching].iter()
+            .chain(&numbers[WINNING..WINNING + YOU_HAVE - matching])
+            .copied()
+            .collect();
+        for i in 0..you_have.len() {
+            let j = i + random(you_have.len() - i);
+            you_have.swap(i, j);
+        }
+
+        writeln!(writer, "Card {id:>width$}: {} | {}", column(winning), column(&you_have))?;
+        matches.push(matching);
+    }
+
+    // every copy of a card adds one copy to each of the next cards it wins
+    let mut copies = vec![1u64; cards];
+    for (i, &matching) in matches.iter().enumerate() {
+        for next in i + 1..cards.min(i + 1 + matching) {
+            copies[next] += copies[i];
+        }
+    }
+
+    let part1 = matches.iter().filter(|&&matching| matching > 0).map(|&matching| 1 << (matching - 1)).sum();
+    let part2 = copies.iter().map(|&copies| copies as u128).sum();
+
+    writer.flush()?;
+    Ok(Answers { part1, part2 })
+}
+
+
+#[cfg(test)]
+mod synthetic {
+    use crate::copies_cascade;
+    use crate::generator::generate;
+    use crate::validation::parse_cards;
+
+    #[test]
+    fn generate_answers() {
+        let mut pile = Vec::new();
+        let answers = generate(&mut pile, 42, 10_000).unwrap();
+
+        let (cards, warnings) = parse_cards(&String::from_utf8(pile).unwrap());
+        assert_eq!(cards.len(), 10_000);
+        assert_eq!(warnings, vec![]);
+        // as in the puzzle input, most cards match none, so the copies stay countable
+        assert!(cards.iter().filter(|card| card.matches() == 0).count() > cards.len() / 2);
+        assert!(cards.iter().all(|card| card.matches() <= 4));
+
+        assert_eq!(cards.iter().map(|card| card.points() as u64).sum::<u64>(), answers.part1);
+        assert_eq!(copies_cascade(&cards).unwrap().iter().map(|&copies| copies as u128).sum::<u128>(), answers.part2);
+    }
+}
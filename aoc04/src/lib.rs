@@ -0,0 +1,457 @@
+use std::collections::HashSet;
+use std::fmt;
+
+pub mod generator;
+pub mod validation;
+
+pub fn copies_cascade(cards: &[Card]) -> Result<Vec<u64>, Overflow> {
+    // The number of copies (the original included) of every card, in the order of the cards.
+    //
+    // A card with n matches adds one copy of each of the next n cards for every copy of itself,
+    // so by the time a card is reached, all its copies are known.
+    // Instead of adding them to every one of the next cards, they are added to a running total
+    // of copies won, and taken off again at the card after the last one won.
+    // Cards beyond the last one are never won.
+    let mut copies: Vec<u64> = Vec::with_capacity(cards.len());
+    let mut won_until: Vec<u64> = vec![0; cards.len() + 1];
+    let mut won: u64 = 0;
+
+    for (i, card) in cards.iter().enumerate() {
+        won -= won_until[i];
+        let current = won.checked_add(1).ok_or(Overflow { card: card.id })?;
+        copies.push(current);
+
+        let to = cards.len().min(i + 1 + card.matches());
+        if to > i + 1 {
+            // everything won so far is won by the next card as well
+            won = won.checked_add(current).ok_or(Overflow { card: cards[i + 1].id })?;
+            won_until[to] += current;
+        }
+    }
+
+    Ok(copies)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Overflow {
+    // the card whose copies do not fit a u64 any more
+    pub card: u32,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "there are more than {} copies of card {}", u64::MAX, self.card)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    pub id: u32,
+    pub numbers_winning: Numbers,
+    pub numbers_you_have: Numbers,
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Card   1: 17 41 48 83 86 |  6  9 17 31 48 53 83 86
+        write!(f, "Card {:>3}: {} | {}", self.id, self.numbers_winning, self.numbers_you_have)
+    }
+}
+
+impl From<&str> for Card {
+    fn from(s: &str) -> Self {
+        Self::parse(s).unwrap()
+    }
+}
+
+impl Card {
+    pub fn parse(s: &str) -> Option<Self> {
+        //       vv
+        // Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+        // ^^^^^^  ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+        // |       split_numbers
+        // split_game
+        let (split_game, split_numbers) = s.split_once(": ")?;
+
+        //     v
+        // Card 1:
+        // ^^^^ ^
+        // _    split_game_id
+        let (_, split_game_id) = split_game.split_once(" ")?;
+
+        //               vvv
+        // 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+        // ^^^^^^^^^^^^^^   ^^^^^^^^^^^^^^^^^^^^^^^
+        // |                split_numbers_you_have
+        // split_numbers_winning
+        let (split_numbers_winning, split_numbers_you_have)
+            = split_numbers.split_once(" | ")?;
+
+        // "1" -> 1
+        let id: u32 = split_game_id.trim().parse().ok()?;
+
+        let numbers_winning = Numbers::parse(split_numbers_winning)?;
+        let numbers_you_have = Numbers::parse(split_numbers_you_have)?;
+
+        Some(Self { id, numbers_winning, numbers_you_have })
+    }
+
+    pub fn numbers_matching(&self) -> HashSet<u32> {
+        self.numbers_winning.numbers.iter()
+            .filter(|&number| self.numbers_you_have.numbers.contains(number))
+            .collect()
+    }
+
+    pub fn matches(&self) -> usize {
+        self.numbers_winning.numbers
+            .intersection_len(&self.numbers_you_have.numbers)
+    }
+
+    pub fn points(&self) -> usize {
+        match self.matches() {
+            0 => 0,
+            x => 2_usize.pow((x - 1) as u32)
+        }
+    }
+}
+
+
+#[derive(Debug, PartialEq)]
+pub struct Numbers {
+    pub numbers: NumberSet,
+    // numbers appearing more than once, in the order of their repetition
+    pub duplicates: Vec<u32>,
+}
+
+impl fmt::Display for Numbers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The numbers in ascending order, followed by the duplicates, each right-aligned in two columns.
+        // Parsing them again finds the same duplicates.
+        let mut numbers: Vec<u32> = self.numbers.iter().collect();
+        numbers.sort_unstable();
+        let numbers: Vec<String> = numbers.iter()
+            .chain(&self.duplicates)
+            .map(|number| format!("{number:>2}"))
+            .collect();
+        write!(f, "{}", numbers.join(" "))
+    }
+}
+
+impl From<&str> for Numbers {
+    fn from(s: &str) -> Self {
+        Self::parse(s).unwrap()
+    }
+}
+
+impl Numbers {
+    pub fn parse(s: &str) -> Option<Self> {
+        //   v  vv v  v  vv v  v
+        // 83 86  6 31 17  9 48 53
+        // ^^ ^^  ^ ^^ ^^  ^ ^^ ^^
+        // numbers
+        let mut numbers = NumberSet::default();
+        let mut duplicates = Vec::new();
+
+        for number in s.split_whitespace() {
+            let number = number.parse().ok()?;
+            if !numbers.insert(number) { duplicates.push(number); }
+        }
+
+        Some(Self { numbers, duplicates })
+    }
+
+    pub fn len(&self) -> usize {
+        // all numbers, duplicates included
+        self.numbers.len() + self.duplicates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.numbers.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NumberSet {
+    // Scratchcard numbers are small, so they usually fit into a single bitset:
+    // bit n is set, iff n is in the set. Matching two sets is a single AND and popcount.
+    Bits(u128),
+    // for sets with a number of 128 or more
+    Set(HashSet<u32>),
+}
+
+impl Default for NumberSet {
+    fn default() -> Self { NumberSet::Bits(0) }
+}
+
+impl NumberSet {
+    pub fn insert(&mut self, number: u32) -> bool {
+        // whether the number was new to the set
+        match self {
+            NumberSet::Bits(bits) if number < u128::BITS => {
+                let bit = 1 << number;
+                let new = *bits & bit == 0;
+                *bits |= bit;
+                new
+            }
+            NumberSet::Bits(_) => {
+                let mut set: HashSet<u32> = self.iter().collect();
+                let new = set.insert(number);
+                *self = NumberSet::Set(set);
+                new
+            }
+            NumberSet::Set(set) => set.insert(number),
+        }
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        match self {
+            NumberSet::Bits(bits) => number < u128::BITS && bits >> number & 1 == 1,
+            NumberSet::Set(set) => set.contains(&number),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            NumberSet::Bits(bits) => bits.count_ones() as usize,
+            NumberSet::Set(set) => set.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=u32> + '_ {
+        let (bits, set) = match self {
+            NumberSet::Bits(bits) => (*bits, None),
+            NumberSet::Set(set) => (0, Some(set)),
+        };
+
+        (0..u128::BITS)
+            .filter(move |&number| bits >> number & 1 == 1)
+            .chain(set.into_iter().flatten().copied())
+    }
+
+    pub fn intersection_len(&self, other: &NumberSet) -> usize {
+        // the number of numbers in both sets
+        match (self, other) {
+            (NumberSet::Bits(a), NumberSet::Bits(b)) => (a & b).count_ones() as usize,
+            _ => {
+                let (smaller, larger) = if self.len() <= other.len() { (self, other) } else { (other, self) };
+                smaller.iter().filter(|&number| larger.contains(number)).count()
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod scratchcards {
+    use std::collections::HashSet;
+    use std::fs::read_to_string;
+
+    use crate::{copies_cascade, Card, NumberSet, Overflow};
+
+    fn example() -> Vec<Card> {
+        read_to_string("src/example").unwrap().lines().map(Card::from).collect()
+    }
+
+    #[test]
+    fn parse() {
+        let card = Card::from("Card  12: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+
+        assert_eq!(card.id, 12);
+        assert_eq!(card.numbers_winning.numbers.iter().collect::<HashSet<_>>(), HashSet::from([41, 48, 83, 86, 17]));
+        assert_eq!(card.numbers_you_have.numbers.iter().collect::<HashSet<_>>(), HashSet::from([83, 86, 6, 31, 17, 9, 48, 53]));
+        assert_eq!(card.numbers_matching(), HashSet::from([48, 83, 86, 17]));
+    }
+
+    #[test]
+    fn print() {
+        let card = Card::from("Card 12: 41 48 83 86 17 41 | 83 86  6 31 17  9 48 53");
+        assert_eq!(card.to_string(), "Card  12: 17 41 48 83 86 41 |  6  9 17 31 48 53 83 86");
+        assert_eq!(Card::from(card.to_string().as_str()), card);
+
+        assert_eq!(Card::from("Card 1:  | 1 2").to_string(), "Card   1:  |  1  2");
+        let large = Card::from("Card 1: 5 200 127 99 | 200");
+        assert_eq!(Card::from(large.to_string().as_str()), large);
+
+        for card in example() {
+            assert_eq!(Card::from(card.to_string().as_str()), card);
+        }
+    }
+
+    #[test]
+    fn points() {
+        // part 1
+        let cards = example();
+
+        assert_eq!(cards.iter().map(Card::matches).collect::<Vec<_>>(), vec![4, 2, 2, 1, 0, 0]);
+        assert_eq!(cards.iter().map(Card::points).collect::<Vec<_>>(), vec![8, 2, 2, 1, 0, 0]);
+        assert_eq!(cards.iter().map(Card::points).sum::<usize>(), 13);
+    }
+
+    #[test]
+    fn cascade() {
+        // part 2
+        let copies = copies_cascade(&example()).unwrap();
+
+        assert_eq!(copies, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(copies.iter().sum::<u64>(), 30);
+
+        assert_eq!(copies_cascade(&[]), Ok(vec![]));
+    }
+
+    fn doubling(count: u32) -> Vec<Card> {
+        // every card wins all the cards after it, doubling the copies from card to card
+        (1..=count)
+            .map(|id| {
+                let numbers: Vec<String> = (1..=count - id).map(|n| n.to_string()).collect();
+                Card::from(format!("Card {id}: {} | {}", numbers.join(" "), numbers.join(" ")).as_str())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cascade_clamped() {
+        // the last cards win cards beyond the end
+        let cards: Vec<Card> = ["Card 1: 1 | 1", "Card 2: 1 2 3 | 1 2 3", "Card 3: 1 2 3 4 5 | 5 4 3 2 1"]
+            .into_iter().map(Card::from).collect();
+
+        assert_eq!(copies_cascade(&cards), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn cascade_large() {
+        let copies = copies_cascade(&doubling(40)).unwrap();
+
+        assert_eq!(copies[0], 1);
+        assert_eq!(copies[33], 1 << 33);
+        assert!(copies[33] > u32::MAX as u64);
+        assert_eq!(copies.iter().sum::<u64>(), (1 << 40) - 1);
+
+        assert_eq!(copies_cascade(&doubling(64)).unwrap()[63], 1 << 63);
+        assert_eq!(copies_cascade(&doubling(70)), Err(Overflow { card: 65 }));
+    }
+
+    #[test]
+    fn number_set() {
+        let mut set = NumberSet::default();
+        assert!(set.insert(0) && set.insert(99) && set.insert(127));
+        assert!(!set.insert(99));
+        assert_eq!(set, NumberSet::Bits(1 | 1 << 99 | 1 << 127));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 99, 127]);
+        assert!(set.contains(127) && !set.contains(128) && !set.contains(1000));
+
+        // too large for the bitset
+        assert!(set.insert(128));
+        assert_eq!(set, NumberSet::Set(HashSet::from([0, 99, 127, 128])));
+        assert!(!set.insert(0));
+        assert_eq!(set.len(), 4);
+
+        let card = Card::from("Card 1: 5 200 127 99 | 200 127 3 99");
+        assert!(matches!(card.numbers_winning.numbers, NumberSet::Set(_)));
+        assert_eq!(card.matches(), 3);
+        assert_eq!(Card::from("Card 1: 5 127 99 | 200 127 3 99").matches(), 2);
+        assert_eq!(Card::from("Card 1: 5 1000 | 1000 5").matches(), 2);
+        assert_eq!(Card::from("Card 1:  | 1 2").matches(), 0);
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use std::collections::{BTreeSet, HashSet};
+
+    use proptest::prelude::*;
+
+    use crate::{copies_cascade, Card, NumberSet};
+    use crate::validation::{parse_cards, Warning};
+
+    // winning numbers and numbers you have, every number on a side at most once
+    type Generated = (BTreeSet<u32>, BTreeSet<u32>);
+
+    fn card() -> impl Strategy<Value=Generated> {
+        (prop::collection::btree_set(1..100u32, 0..10), prop::collection::btree_set(1..100u32, 0..25))
+    }
+
+    fn text(id: usize, (winning, you_have): &Generated) -> String {
+        // Card   1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+        let column = |numbers: &BTreeSet<u32>| numbers.iter().map(|number| format!("{number:>2}")).collect::<Vec<_>>().join(" ");
+        format!("Card {id:>3}: {} | {}", column(winning), column(you_have))
+    }
+
+    fn numbers(set: &NumberSet) -> HashSet<u32> {
+        set.iter().collect()
+    }
+
+    proptest! {
+        #[test]
+        fn parse_round_trip(generated in card(), id in 1..1000usize) {
+            let card = Card::from(text(id, &generated).as_str());
+
+            prop_assert_eq!(card.id as usize, id);
+            prop_assert_eq!(numbers(&card.numbers_winning.numbers), generated.0.iter().copied().collect());
+            prop_assert_eq!(numbers(&card.numbers_you_have.numbers), generated.1.iter().copied().collect());
+            prop_assert!(card.numbers_winning.duplicates.is_empty() && card.numbers_you_have.duplicates.is_empty());
+            prop_assert_eq!(card.to_string(), text(id, &generated));
+        }
+
+        #[test]
+        fn print_round_trip(winning in prop::collection::vec(0..300u32, 0..10), you_have in prop::collection::vec(0..300u32, 0..25)) {
+            // any numbers, duplicates and numbers too large for the bitsets included
+            let numbers = |numbers: Vec<u32>| numbers.iter().map(u32::to_string).collect::<Vec<_>>().join(" ");
+            let card = Card::from(format!("Card 7: {} | {}", numbers(winning), numbers(you_have)).as_str());
+
+            prop_assert_eq!(Card::from(card.to_string().as_str()), card);
+        }
+
+        #[test]
+        fn points_double_with_every_match(generated in card()) {
+            let card = Card::from(text(1, &generated).as_str());
+            let matching: HashSet<u32> = generated.0.intersection(&generated.1).copied().collect();
+
+            prop_assert_eq!(card.matches(), matching.len());
+            prop_assert_eq!(card.numbers_matching(), matching);
+            match card.matches() {
+                0 => prop_assert_eq!(card.points(), 0),
+                matches => prop_assert_eq!(card.points(), 1 << (matches - 1)),
+            }
+        }
+
+        #[test]
+        fn bitsets_agree_with_hash_sets(a in prop::collection::vec(0..300u32, 0..40), b in prop::collection::vec(0..300u32, 0..40)) {
+            // numbers from 128 on turn the bitset into a hash set
+            let (mut set_a, mut set_b) = (NumberSet::default(), NumberSet::default());
+            let (mut hash_a, mut hash_b) = (HashSet::new(), HashSet::new());
+            for &number in &a { prop_assert_eq!(set_a.insert(number), hash_a.insert(number)); }
+            for &number in &b { prop_assert_eq!(set_b.insert(number), hash_b.insert(number)); }
+
+            prop_assert_eq!(numbers(&set_a), hash_a.clone());
+            prop_assert_eq!(set_a.len(), hash_a.len());
+            prop_assert!((0..300).all(|number| set_a.contains(number) == hash_a.contains(&number)));
+            prop_assert_eq!(set_a.intersection_len(&set_b), hash_a.intersection(&hash_b).count());
+            prop_assert_eq!(set_b.intersection_len(&set_a), hash_a.intersection(&hash_b).count());
+        }
+
+        #[test]
+        fn cascade_agrees_with_adding_every_copy(generated in prop::collection::vec(card(), 0..30)) {
+            let text: Vec<String> = generated.iter().enumerate().map(|(i, card)| text(i + 1, card)).collect();
+            let (cards, warnings) = parse_cards(&text.join("\n"));
+            prop_assert_eq!(cards.len(), generated.len());
+            // cards with other numbers of numbers than the first one are still scored
+            prop_assert!(warnings.iter().all(|warning| matches!(warning, Warning::ColumnCount { .. })), "{:?}", warnings);
+
+            // every copy of a card adds one copy to each of the next cards it wins
+            let mut copies = vec![1u64; cards.len()];
+            for (i, card) in cards.iter().enumerate() {
+                for next in i + 1..cards.len().min(i + 1 + card.matches()) {
+                    copies[next] += copies[i];
+                }
+            }
+
+            prop_assert_eq!(copies_cascade(&cards), Ok(copies));
+        }
+    }
+}
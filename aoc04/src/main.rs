@@ -0,0 +1,29 @@
+use std::env::args;
+use std::fs::read_to_string;
+
+use aoc04::copies_cascade;
+use aoc04::generator::generate;
+use aoc04::validation::parse_cards;
+use seeded::generate_command;
+
+fn main() {
+    // Usage: aoc04 <input>
+    //        aoc04 generate <cards> [<seed>] > <input>    (the answers go to stderr)
+    let usage = "usage: aoc04 <input> | aoc04 generate <cards> [<seed>]";
+    let args: Vec<String> = args().collect();
+
+    if generate_command(&args[1..], usage, 1, |sizes, seed, writer| {
+        generate(writer, seed, sizes[0].parse().expect("cards must be a number"))
+    }) {
+        return;
+    }
+
+    let (cards, warnings) = parse_cards(&read_to_string(args.get(1).expect(usage)).unwrap());
+    for warning in warnings { eprintln!("{}", warning); }
+
+    println!("part 1: {}", cards.iter().map(|card| card.points() as u64).sum::<u64>());
+    match copies_cascade(&cards) {
+        Ok(copies) => println!("part 2: {}", copies.iter().map(|&copies| copies as u128).sum::<u128>()),
+        Err(overflow) => println!("part 2: {}", overflow),
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+
+use crate::Card;
+
+pub fn parse_cards(s: &str) -> (Vec<Card>, Vec<Warning>) {
+    // All cards that can be read, and everything suspicious about them.
+    // Cards are kept even if there is a warning about them, only unreadable lines are skipped:
+    // - numbers appearing twice on the same side count only once
+    // - the cascade of copies goes by the position of the cards, not by their ids
+    let mut cards: Vec<Card> = Vec::new();
+    let mut warnings = Vec::new();
+    // (number of winning numbers, number of numbers you have, positions of ':' and '|') of the first card
+    let mut layout: Option<(usize, usize, Option<usize>, Option<usize>)> = None;
+
+    for (i, card_as_str) in s.lines().enumerate() {
+        let line = i + 1;
+        if card_as_str.trim().is_empty() { continue; }
+
+        let Some(card) = Card::parse(card_as_str) else {
+            warnings.push(Warning::Malformed { line, content: card_as_str.to_string() });
+            continue;
+        };
+        let id = card.id;
+
+        for (side, numbers) in [(Side::Winning, &card.numbers_winning), (Side::YouHave, &card.numbers_you_have)] {
+            for &number in &numbers.duplicates {
+                warnings.push(Warning::DuplicateNumber { line, card: id, side, number });
+            }
+        }
+
+        let expected = cards.last().map_or(1, |previous| previous.id + 1);
+        if id > expected {
+            warnings.push(Warning::MissingCards { line, from: expected, to: id - 1 });
+        } else if id < expected {
+            warnings.push(Warning::OutOfOrder { line, card: id, previous: expected - 1 });
+        }
+
+        let card_layout = (card.numbers_winning.len(), card.numbers_you_have.len(), card_as_str.find(':'), card_as_str.find('|'));
+        let &mut (winning, you_have, colon, bar) = layout.get_or_insert(card_layout);
+        if (card_layout.0, card_layout.1) != (winning, you_have) {
+            warnings.push(Warning::ColumnCount {
+                line,
+                card: id,
+                winning: card_layout.0,
+                you_have: card_layout.1,
+                expected: (winning, you_have),
+            });
+        } else if (card_layout.2, card_layout.3) != (colon, bar) {
+            warnings.push(Warning::Misaligned { line, card: id });
+        }
+
+        cards.push(card);
+    }
+
+    (cards, warnings)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side { Winning, YouHave }
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Warning {
+    // the line is skipped
+    Malformed { line: usize, content: String },
+    DuplicateNumber { line: usize, card: u32, side: Side, number: u32 },
+    // the cards with the ids from..=to are missing before this line
+    MissingCards { line: usize, from: u32, to: u32 },
+    OutOfOrder { line: usize, card: u32, previous: u32 },
+    // a card with a different number of winning numbers or numbers you have than the first card
+    ColumnCount { line: usize, card: u32, winning: usize, you_have: usize, expected: (usize, usize) },
+    // a card with the numbers of the first card, not lined up with them
+    Misaligned { line: usize, card: u32 },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Warning::Malformed { line, content } =>
+                write!(f, "line {line}: expected 'Card <id>: <numbers> | <numbers>', got '{content}'"),
+            Warning::DuplicateNumber { line, card, side, number } => {
+                let side = match side {
+                    Side::Winning => "winning numbers",
+                    Side::YouHave => "numbers you have",
+                };
+                write!(f, "line {line}: card {card} has {number} more than once in its {side}")
+            }
+            Warning::MissingCards { line, from, to } if from == to =>
+                write!(f, "line {line}: card {from} is missing"),
+            Warning::MissingCards { line, from, to } =>
+                write!(f, "line {line}: cards {from} to {to} are missing"),
+            Warning::OutOfOrder { line, card, previous } =>
+                write!(f, "line {line}: card {card} comes after card {previous}"),
+            Warning::ColumnCount { line, card, winning, you_have, expected: (expected_winning, expected_you_have) } =>
+                write!(f, "line {line}: card {card} has {winning} | {you_have} numbers, \
+                           expected {expected_winning} | {expected_you_have} like the first card"),
+            Warning::Misaligned { line, card } =>
+                write!(f, "line {line}: the columns of card {card} do not line up with the first card"),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod warnings {
+    use std::fs::read_to_string;
+
+    use crate::copies_cascade;
+    use crate::validation::{parse_cards, Side, Warning};
+
+    #[test]
+    fn example() {
+        let (cards, warnings) = parse_cards(&read_to_string("src/example").unwrap());
+
+        assert_eq!(cards.len(), 6);
+        assert_eq!(warnings, vec![]);
+    }
+
+    #[test]
+    fn duplicates() {
+        let (cards, warnings) = parse_cards("Card 1: 41 48 41 | 41 48  6 48 48");
+
+        assert_eq!(warnings, vec![
+            Warning::DuplicateNumber { line: 1, card: 1, side: Side::Winning, number: 41 },
+            Warning::DuplicateNumber { line: 1, card: 1, side: Side::YouHave, number: 48 },
+            Warning::DuplicateNumber { line: 1, card: 1, side: Side::YouHave, number: 48 },
+        ]);
+        // still scored, each number counting once
+        assert_eq!(cards[0].points(), 2);
+        assert_eq!(warnings[2].to_string(), "line 1: card 1 has 48 more than once in its numbers you have");
+    }
+
+    #[test]
+    fn ids() {
+        let (cards, warnings) = parse_cards("Card 1: 1 | 1\nCard 2: 2 | 2\nCard 5: 3 | 3\n\nCard 4: 4 | 4\nCard 6: 5 | 5");
+
+        assert_eq!(warnings, vec![
+            Warning::MissingCards { line: 3, from: 3, to: 4 },
+            Warning::OutOfOrder { line: 5, card: 4, previous: 5 },
+            Warning::MissingCards { line: 6, from: 5, to: 5 },
+        ]);
+        assert_eq!(warnings[0].to_string(), "line 3: cards 3 to 4 are missing");
+        assert_eq!(warnings[2].to_string(), "line 6: card 5 is missing");
+        assert_eq!(copies_cascade(&cards), Ok(vec![1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn columns() {
+        let (cards, warnings) = parse_cards("\
+Card 1: 41 48 83 | 83 86  6 31
+Card 2: 13 32 | 61 30 68 82
+Card 3: 1 21 53 | 69 82 63 72
+Card 4: one 2 | 3 4
+Card 5:  8  9 10 | 11 12 13 14");
+
+        assert_eq!(warnings, vec![
+            Warning::ColumnCount { line: 2, card: 2, winning: 2, you_have: 4, expected: (3, 4) },
+            Warning::Misaligned { line: 3, card: 3 },
+            Warning::Malformed { line: 4, content: "Card 4: one 2 | 3 4".to_string() },
+            Warning::MissingCards { line: 5, from: 4, to: 4 },
+        ]);
+        assert_eq!(cards.iter().map(|card| card.id).collect::<Vec<_>>(), vec![1, 2, 3, 5]);
+        assert_eq!(cards.iter().map(|card| card.points()).collect::<Vec<_>>(), vec![1, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,25 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc05a::{lowest_location, Greenhouse};
+use aoc05a::generator::{generate, SPACE};
+
+fn phases(c: &mut Criterion) {
+    let mut almanac = Vec::new();
+    generate(&mut almanac, 0x2023_1205, 1000, 10, SPACE).unwrap();
+    let almanac = String::from_utf8(almanac).unwrap();
+
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("aoc05a 1000 ranges per map", |b| b.iter(|| Greenhouse::from(black_box(almanac.trim()))));
+    group.finish();
+
+    let greenhouse = Greenhouse::from(almanac.trim());
+
+    let mut group = c.benchmark_group("solve");
+    group.bench_function("aoc05a 1000 ranges per map", |b| b.iter(|| lowest_location(black_box(&greenhouse))));
+    group.finish();
+}
+
+criterion_group!(benches, phases);
+criterion_main!(benches);
@@ -0,0 +1,154 @@
+use std::io::{self, Write};
+
+// numbers in the puzzle input go up to about 2^32
+pub const SPACE: u64 = 1 << 32;
+
+static MAPS: [&str; 7] = [
+    "seed-to-soil", "soil-to-fertilizer", "fertilizer-to-water", "water-to-light",
+    "light-to-temperature", "temperature-to-humidity", "humidity-to-location",
+];
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    // lowest location of all seeds
+    pub part1: u64,
+    // lowest location of all seed ranges
+    pub part2: u64,
+}
+
+impl seeded::Answers for Answers {
+    fn parts(&self) -> Vec<u128> { vec![self.part1.into(), self.part2.into()] }
+}
+
+pub fn generate<W: Write>(mut writer: W, seed: u64, ranges: usize, pairs: usize, space: u64) -> io::Result<Answers> {
+    // An almanac like the puzzle input: every map cuts the numbers below space into ranges,
+    // and moves these ranges to shuffled places, so every number has exactly one destination.
+    // Seeds come in pairs, which are read as seed range start and length in part 2.
+    //
+    // Part 1 looks up every seed, part 2 follows whole ranges through the maps,
+    // splitting them where the ranges of a map end, instead of looking up every seed in them.
+    assert!(ranges >= 1 && pairs >= 1 && ranges as u64 <= space);
+
+    let mut random = seeded::random_u64(seed);
+
+    let mut seeds = Vec::with_capacity(2 * pairs);
+    for _ in 0..pairs {
+        let length = 1 + random((space / (2 * pairs as u64)).max(1));
+        let start = random(space - length + 1);
+        seeds.extend([start, length]);
+    }
+
+    // (destination start, source start, length) of every range of every map
+    let mut maps: Vec<Vec<(u64, u64, u64)>> = Vec::with_capacity(MAPS.len());
+    for _ in MAPS {
+        let mut cuts = vec![0, space];
+        while cuts.len() < ranges + 1 {
+            let cut = 1 + random(space - 1);
+            if !cuts.contains(&cut) { cuts.push(cut); }
+        }
+        cuts.sort_unstable();
+
+        let mut sources: Vec<(u64, u64)> = cuts.windows(2).map(|cut| (cut[0], cut[1] - cut[0])).collect();
+        for i in 0..sources.len() {
+            let j = i + random((sources.len() - i) as u64) as usize;
+            sources.swap(i, j);
+        }
+
+        let mut destination = 0;
+        let mut map = Vec::with_capacity(ranges);
+        for (source, length) in sources {
+            map.push((destination, source, length));
+            destination += length;
+        }
+        for i in 0..map.len() {
+            let j = i + random((map.len() - i) as u64) as usize;
+            map.swap(i, j);
+        }
+        maps.push(map);
+    }
+
+    let seeds_line: Vec<String> = seeds.iter().map(u64::to_string).collect();
+    write!(writer, "seeds: {}\n\n", seeds_line.join(" "))?;
+    for (i, (name, map)) in MAPS.iter().zip(&maps).enumerate() {
+        writeln!(writer, "{name} map:")?;
+        for (destination, source, length) in map {
+            writeln!(writer, "{destination} {source} {length}")?;
+        }
+        if i + 1 < MAPS.len() { writeln!(writer)?; }
+    }
+
+    let part1 = seeds.iter()
+        .map(|&seed| maps.iter().fold(seed, |source, map| destination(map, source)))
+        .min()
+        .unwrap();
+
+    // seed ranges, start and end (exclusive)
+    let mut intervals: Vec<(u64, u64)> = seeds.chunks(2).map(|pair| (pair[0], pair[0] + pair[1])).collect();
+    for map in &maps {
+        intervals = intervals.into_iter().flat_map(|interval| split(map, interval)).collect();
+    }
+    let part2 = intervals.iter().map(|&(start, _)| start).min().unwrap();
+
+    writer.flush()?;
+    Ok(Answers { part1, part2 })
+}
+
+fn destination(map: &[(u64, u64, u64)], source: u64) -> u64 {
+    map.iter()
+        .find(|&&(_, start, length)| start <= source && source < start + length)
+        .map_or(source, |&(destination, start, _)| destination + (source - start))
+}
+
+fn split(map: &[(u64, u64, u64)], (mut start, end): (u64, u64)) -> Vec<(u64, u64)> {
+    // The interval is cut at the start and end of every range of the map.
+    // Within each piece, every number moves by the same amount (or not at all).
+    let mut pieces = Vec::new();
+
+    while start < end {
+        let piece_end = map.iter()
+            .flat_map(|&(_, source, length)| [source, source + length])
+            .filter(|&cut| start < cut && cut < end)
+            .min()
+            .unwrap_or(end);
+
+        let moved = destination(map, start);
+        pieces.push((moved, moved + (piece_end - start)));
+        start = piece_end;
+    }
+
+    pieces
+}
+
+
+#[cfg(test)]
+mod synthetic {
+    use crate::generator::generate;
+    use crate::{lowest_location, Greenhouse, Range};
+
+    #[test]
+    fn generate_answers() {
+        let mut almanac = Vec::new();
+        let answers = generate(&mut almanac, 42, 20, 5, 100_000).unwrap();
+
+        let greenhouse = Greenhouse::from(String::from_utf8(almanac).unwrap().trim());
+        assert_eq!(greenhouse.maps.len(), 7);
+        assert!(greenhouse.maps.values().all(|map| map.ranges.len() == 20));
+        // the sources of every map, and its destinations, cover all numbers below the space once
+        for map in greenhouse.maps.values() {
+            for start in [|range: &Range| range.source_start, |range: &Range| range.destination_start] {
+                let mut ranges: Vec<&Range> = map.ranges.iter().collect();
+                ranges.sort_by_key(|range| start(range));
+                let end = ranges.iter().try_fold(0, |end, range| (start(range) == end).then_some(end + range.length));
+                assert_eq!(end, Some(100_000));
+            }
+        }
+        assert_eq!(lowest_location(&greenhouse), answers.part1);
+
+        // every seed of every range, as day 5b does it
+        let seeds: Vec<u64> = greenhouse.seeds.chunks(2)
+            .flat_map(|pair| pair[0]..pair[0] + pair[1])
+            .collect();
+        let ranges = Greenhouse { seeds, ..greenhouse };
+        assert_eq!(lowest_location(&ranges), answers.part2);
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::{BTreeMap};
+use std::fmt;
+
+pub mod generator;
+
+pub fn lowest_location(greenhouse: &Greenhouse) -> u64 {
+    let mut lowest: u64 = u64::MAX;
+
+    for &seed in &greenhouse.seeds {
+        let soil = greenhouse.maps[&0].get_destination(seed);
+        let fertilizer = greenhouse.maps[&1].get_destination(soil);
+        let water = greenhouse.maps[&2].get_destination(fertilizer);
+        let light = greenhouse.maps[&3].get_destination(water);
+        let temperature = greenhouse.maps[&4].get_destination(light);
+        let humidity = greenhouse.maps[&5].get_destination(temperature);
+        let location = greenhouse.maps[&6].get_destination(humidity);
+        // dbg!(seed, soil, fertilizer,water, light, temperature, humidity, location);
+
+        lowest = lowest.min(location);
+    }
+
+    lowest
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Greenhouse {
+    seeds: Vec<u64>,
+    maps: BTreeMap<usize, Map>, // using BtreeMap to preserve (and nicely show) order
+}
+
+impl fmt::Display for Greenhouse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let seeds: Vec<String> = self.seeds.iter().map(u64::to_string).collect();
+        let maps: Vec<String> = self.maps.values().map(Map::to_string).collect();
+        write!(f, "seeds: {}\n\n{}", seeds.join(" "), maps.join("\n\n"))
+    }
+}
+
+impl Greenhouse {
+    pub fn from(s: &str) -> Greenhouse {
+        //                   vvvv
+        // seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n [...]
+        // ^^^^^^^^^^^^^^^^^^    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+        // split_seeds           split_maps
+        let (split_seeds, split_maps) = s.split_once("\n\n").unwrap();
+
+        //      vv
+        // seeds: 79 14 55 13
+        // ^^^^^  ^^^^^^^^^^^
+        // _      split_seeds_numbers
+        let (_, split_seeds_numbers) = split_seeds.split_once(": ").unwrap();
+
+        //   v  v  v
+        // 79 14 55 13
+        // ^^ ^^ ^^ ^^
+        // seeds
+        let seeds: Vec<u64> = split_seeds_numbers
+            .split_whitespace()
+            .map(|x| x.parse().unwrap())
+            .collect();
+
+        //                                     vvvv
+        // seed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n [...]
+        // ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+        // split_maps_maps[0]                      split_maps_maps[1]
+        // ...split_maps_maps[n]
+        let split_maps_maps: Vec<&str> = split_maps.split("\n\n").collect();
+
+        let mut maps: BTreeMap<usize, Map> = BTreeMap::new();
+        for (i, split_maps_map) in split_maps_maps.into_iter().enumerate() {
+            maps.insert(i, Map::from(split_maps_map));
+        }
+
+        Greenhouse { seeds, maps }
+    }
+}
+
+
+#[derive(Debug, PartialEq)]
+struct Map {
+    // e.g. "seed-to-soil"
+    name: String,
+    ranges: Vec<Range>,
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ranges: Vec<String> = self.ranges.iter().map(Range::to_string).collect();
+        write!(f, "{} map:\n{}", self.name, ranges.join("\n"))
+    }
+}
+
+impl Map {
+    fn from(s: &str) -> Map {
+        //                 vvv
+        // seed-to-soil map:\n50 98 2\n52 50 48
+        // ^^^^^^^^^^^^^^^^   ^^^^^^^^^^^^^^^^^
+        // split_name         split_ranges
+        let (split_name, split_ranges) = s.split_once(":\n").unwrap();
+        let name = split_name.strip_suffix(" map").unwrap_or(split_name).to_string();
+
+
+        //        vv
+        // 50 98 2\n52 50 48
+        // ^^^^^^^  ^^^^^^^^
+        // range[0] range[n]
+        let mut ranges: Vec<Range> = Vec::new();
+        for range in split_ranges.split("\n") {
+            ranges.push(Range::from(range))
+        }
+
+        Self { name, ranges }
+    }
+
+    fn get_destination(&self, source: u64) -> u64 {
+        // Check which range applies (if any)
+        match self.find_range_id(source) {
+            // If a Range can handle the mapping, let the Range figure out the destination
+            Some(id) => self.ranges[id].get_destination(source),
+            // If no Range can handle the mapping, output is input
+            None => source
+        }
+    }
+
+    fn find_range_id(&self, source: u64) -> Option<usize> {
+        self.ranges.iter()
+            .enumerate()
+            .find(|(_, x)| x.applies(source))
+            .map(|(id, _)| id)
+    }
+}
+
+
+#[derive(Debug, PartialEq)]
+struct Range {
+    destination_start: u64,
+    source_start: u64,
+    length: u64,
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.destination_start, self.source_start, self.length)
+    }
+}
+
+impl Range {
+    fn from(s: &str) -> Self {
+        // 50 98 2
+        // ^^ ^^ ^
+        // |  |  length
+        // |  source_start
+        // destination_start
+        let mut split = s.split_whitespace();
+        let destination_start: u64 = split.next().unwrap().parse().unwrap();
+        let source_start: u64 = split.next().unwrap().parse().unwrap();
+        let length: u64 = split.next().unwrap().parse().unwrap();
+        // not happy...
+
+        Range { destination_start, source_start, length }
+    }
+
+    fn applies(&self, source: u64) -> bool {
+        // A Range applies iff the source is within the range
+        // A Range is defined by its start and end (exclusive)
+
+        let start = self.source_start;
+        let end = self.source_start + self.length;
+
+        start <= source && source < end
+    }
+
+    fn get_destination(&self, source: u64) -> u64 {
+        source - self.source_start + self.destination_start
+    }
+}
+
+
+#[cfg(test)]
+mod ranges {
+    use std::fs::read_to_string;
+
+    use crate::{lowest_location, Greenhouse, Map};
+
+    #[test]
+    fn example() {
+        let greenhouse = Greenhouse::from(read_to_string("src/example").unwrap().trim());
+        assert_eq!(lowest_location(&greenhouse), 35);
+    }
+
+    #[test]
+    fn ranges_end_before_start_plus_length() {
+        // 98 and 99 are mapped, 100 is not
+        let map = Map::from("seed-to-soil map:\n50 98 2");
+
+        assert_eq!(map.get_destination(97), 97);
+        assert_eq!(map.get_destination(98), 50);
+        assert_eq!(map.get_destination(99), 51);
+        assert_eq!(map.get_destination(100), 100);
+    }
+}
+
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use crate::{Greenhouse, Map};
+
+    static MAPS: [&str; 7] = [
+        "seed-to-soil", "soil-to-fertilizer", "fertilizer-to-water", "water-to-light",
+        "light-to-temperature", "temperature-to-humidity", "humidity-to-location",
+    ];
+
+    // (destination start, source start, length)
+    type Ranges = Vec<(u64, u64, u64)>;
+
+    fn ranges() -> impl Strategy<Value=Ranges> {
+        prop::collection::vec((0..1000u64, 0..1000u64, 1..100u64), 1..6)
+    }
+
+    fn almanac() -> impl Strategy<Value=(Vec<u64>, Vec<Ranges>)> {
+        (prop::collection::vec(0..1100u64, 1..10), prop::collection::vec(ranges(), 7))
+    }
+
+    fn text(seeds: &[u64], maps: &[Ranges]) -> String {
+        let seeds: Vec<String> = seeds.iter().map(u64::to_string).collect();
+        let maps: Vec<String> = MAPS.iter().zip(maps)
+            .map(|(name, ranges)| {
+                let ranges: Vec<String> = ranges.iter()
+                    .map(|(destination, source, length)| format!("{destination} {source} {length}"))
+                    .collect();
+                format!("{name} map:\n{}", ranges.join("\n"))
+            })
+            .collect();
+        format!("seeds: {}\n\n{}", seeds.join(" "), maps.join("\n\n"))
+    }
+
+    #[test]
+    fn print_example() {
+        let example = std::fs::read_to_string("src/example").unwrap();
+        let greenhouse = Greenhouse::from(example.trim());
+
+        assert_eq!(greenhouse.maps[&0].name, "seed-to-soil");
+        assert_eq!(greenhouse.to_string(), example.trim());
+        assert_eq!(Greenhouse::from(greenhouse.to_string().as_str()), greenhouse);
+    }
+
+    proptest! {
+        #[test]
+        fn parse_round_trip((seeds, maps) in almanac()) {
+            let text = text(&seeds, &maps);
+            let greenhouse = Greenhouse::from(text.as_str());
+            prop_assert_eq!(greenhouse.to_string(), text);
+
+            prop_assert_eq!(&greenhouse.seeds, &seeds);
+            prop_assert_eq!(greenhouse.maps.keys().copied().collect::<Vec<_>>(), (0..7).collect::<Vec<_>>());
+            for (map, ranges) in greenhouse.maps.values().zip(&maps) {
+                let parsed: Ranges = map.ranges.iter()
+                    .map(|range| (range.destination_start, range.source_start, range.length))
+                    .collect();
+                prop_assert_eq!(&parsed, ranges);
+            }
+        }
+
+        #[test]
+        fn destination_of_the_first_range_containing_the_source(ranges in ranges(), source in 0..1200u64) {
+            let text = text(&[0], std::slice::from_ref(&ranges));
+            let map = Map::from(text.split_once("\n\n").unwrap().1);
+
+            let expect = ranges.iter()
+                .find(|&&(_, start, length)| (start..start + length).contains(&source))
+                .map_or(source, |&(destination, start, _)| destination + (source - start));
+            prop_assert_eq!(map.get_destination(source), expect);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc05a::generator::generate;
+use aoc05b::{lowest_location, Greenhouse};
+
+fn phases(c: &mut Criterion) {
+    // Seeds are looked up one by one, so the seed ranges are kept small (100000 numbers in all):
+    // the puzzle input has billions of seeds, which this never finishes.
+    let mut almanac = Vec::new();
+    generate(&mut almanac, 0x2023_1205, 1000, 10, 100_000).unwrap();
+    let almanac = String::from_utf8(almanac).unwrap();
+
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("aoc05b 1000 ranges per map", |b| b.iter(|| Greenhouse::from(black_box(almanac.trim()))));
+    group.finish();
+
+    let greenhouse = Greenhouse::from(almanac.trim());
+
+    let mut group = c.benchmark_group("solve");
+    group.sample_size(10);
+    group.bench_function("aoc05b 1000 ranges per map, brute force", |b| b.iter(|| lowest_location(black_box(&greenhouse))));
+    group.finish();
+}
+
+criterion_group!(benches, phases);
+criterion_main!(benches);
@@ -0,0 +1,241 @@
+use std::collections::{BTreeMap};
+use std::fmt;
+use std::ops::RangeInclusive;
+use itertools::Itertools;
+
+pub fn lowest_location(greenhouse: &Greenhouse) -> u64 {
+    /*
+    This solution works for example input,
+     but does not finish within any acceptable time for puzzle input
+     */
+    let mut lowest: u64 = u64::MAX;
+
+    for seed_range in greenhouse.seed_ranges.iter().cloned() {
+        for seed in seed_range {
+            let soil = greenhouse.maps[&0].get_destination(seed);
+            let fertilizer = greenhouse.maps[&1].get_destination(soil);
+            let water = greenhouse.maps[&2].get_destination(fertilizer);
+            let light = greenhouse.maps[&3].get_destination(water);
+            let temperature = greenhouse.maps[&4].get_destination(light);
+            let humidity = greenhouse.maps[&5].get_destination(temperature);
+            let location = greenhouse.maps[&6].get_destination(humidity);
+            // dbg!(seed, soil, fertilizer,water, light, temperature, humidity, location);
+
+            lowest = lowest.min(location);
+        }
+    }
+
+    lowest
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Greenhouse {
+    seed_ranges: Vec<RangeInclusive<u64>>,
+    maps: BTreeMap<usize, Map>, // using BtreeMap to preserve (and nicely show) order
+}
+
+impl fmt::Display for Greenhouse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // the ranges as start and length
+        let seeds: Vec<String> = self.seed_ranges.iter()
+            .map(|range| format!("{} {}", range.start(), range.end() - range.start() + 1))
+            .collect();
+        let maps: Vec<String> = self.maps.values().map(Map::to_string).collect();
+        write!(f, "seeds: {}\n\n{}", seeds.join(" "), maps.join("\n\n"))
+    }
+}
+
+impl Greenhouse {
+    pub fn from(s: &str) -> Greenhouse {
+        //                   vvvv
+        // seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n [...]
+        // ^^^^^^^^^^^^^^^^^^    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+        // split_seeds           split_maps
+        let (split_seeds, split_maps) = s.split_once("\n\n").unwrap();
+
+        //      vv
+        // seeds: 79 14 55 13
+        // ^^^^^  ^^^^^^^^^^^
+        // _      split_seeds_numbers
+        let (_, split_seeds_numbers) = split_seeds.split_once(": ").unwrap();
+
+        //      v
+        // 79 14 55 13
+        // ^^^^^ ^^^^^
+        // |     seed_range[1]
+        // seed_range[0]
+        // (line can contain many more seeds than just two pairs)
+        // Given are seed range start and the length of the range.
+        // The end (inclusive) therefore is start + length - 1
+        let seed_ranges: Vec<RangeInclusive<u64>> = split_seeds_numbers
+            .split_whitespace()
+            .map(|number_str| number_str.parse::<u64>().unwrap())
+            .tuples::<(u64, u64)>()
+            .map(|(start, length)|
+                // Range start, Range end (start plus length, minus the start itself)
+                RangeInclusive::new(start, start + length - 1))
+            .collect();
+
+        //                                     vvvv
+        // seed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n [...]
+        // ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+        // split_maps_maps[0]                      split_maps_maps[1]
+        // ...split_maps_maps[n]
+        let split_maps_maps: Vec<&str> = split_maps.split("\n\n").collect();
+
+        let mut maps: BTreeMap<usize, Map> = BTreeMap::new();
+        for (i, split_maps_map) in split_maps_maps.into_iter().enumerate() {
+            maps.insert(i, Map::from(split_maps_map));
+        }
+
+        Greenhouse { seed_ranges, maps }
+    }
+}
+
+
+#[derive(Debug, PartialEq)]
+struct Map {
+    // e.g. "seed-to-soil"
+    name: String,
+    ranges: Vec<Range>,
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ranges: Vec<String> = self.ranges.iter().map(Range::to_string).collect();
+        write!(f, "{} map:\n{}", self.name, ranges.join("\n"))
+    }
+}
+
+impl Map {
+    fn from(s: &str) -> Map {
+        //                 vvv
+        // seed-to-soil map:\n50 98 2\n52 50 48
+        // ^^^^^^^^^^^^^^^^   ^^^^^^^^^^^^^^^^^
+        // split_name         split_ranges
+        let (split_name, split_ranges) = s.split_once(":\n").unwrap();
+        let name = split_name.strip_suffix(" map").unwrap_or(split_name).to_string();
+
+
+        //        vv
+        // 50 98 2\n52 50 48
+        // ^^^^^^^  ^^^^^^^^
+        // range[0] range[n]
+        let mut ranges: Vec<Range> = Vec::new();
+        for range in split_ranges.split("\n") {
+            ranges.push(Range::from(range))
+        }
+
+        Self { name, ranges }
+    }
+
+    fn get_destination(&self, source: u64) -> u64 {
+        // Check which range applies (if any)
+        match self.find_range_id(source) {
+            // If a Range can handle the mapping, let the Range figure out the destination
+            Some(id) => self.ranges[id].get_destination(source),
+            // If no Range can handle the mapping, output is input
+            None => source
+        }
+    }
+
+    fn find_range_id(&self, source: u64) -> Option<usize> {
+        self.ranges.iter()
+            .enumerate()
+            .find(|(_, x)| x.applies(source))
+            .map(|(id, _)| id)
+    }
+}
+
+
+#[derive(Debug, PartialEq)]
+struct Range {
+    destination_start: u64,
+    source_start: u64,
+    length: u64,
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.destination_start, self.source_start, self.length)
+    }
+}
+
+impl Range {
+    fn from(s: &str) -> Self {
+        // 50 98 2
+        // ^^ ^^ ^
+        // |  |  length
+        // |  source_start
+        // destination_start
+        let mut split = s.split_whitespace();
+        let destination_start: u64 = split.next().unwrap().parse().unwrap();
+        let source_start: u64 = split.next().unwrap().parse().unwrap();
+        let length: u64 = split.next().unwrap().parse().unwrap();
+        // not happy...
+
+        Range { destination_start, source_start, length }
+    }
+
+    fn applies(&self, source: u64) -> bool {
+        // A Range applies iff the source is within the range
+        // A Range is defined by its start and end (exclusive)
+
+        let start = self.source_start;
+        let end = self.source_start + self.length;
+
+        start <= source && source < end
+    }
+
+    fn get_destination(&self, source: u64) -> u64 {
+        source - self.source_start + self.destination_start
+    }
+}
+
+
+#[cfg(test)]
+mod ranges {
+    use std::fs::read_to_string;
+
+    use crate::{lowest_location, Greenhouse};
+
+    #[test]
+    fn example() {
+        let greenhouse = Greenhouse::from(read_to_string("src/example").unwrap().trim());
+        assert_eq!(lowest_location(&greenhouse), 46);
+    }
+
+    #[test]
+    fn seed_ranges_hold_length_seeds() {
+        // 79 to 92 and 55 to 67, the seeds right after them belong to no range
+        let greenhouse = Greenhouse::from("seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2");
+
+        assert_eq!(greenhouse.seed_ranges, vec![79..=92, 55..=67]);
+        assert_eq!(greenhouse.to_string(), "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2");
+    }
+}
+
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use crate::Greenhouse;
+
+    proptest! {
+        #[test]
+        fn seed_ranges(pairs in prop::collection::vec((0..1000u64, 1..100u64), 1..10)) {
+            let seeds: Vec<String> = pairs.iter().map(|(start, length)| format!("{start} {length}")).collect();
+            let text = format!("seeds: {}\n\nseed-to-soil map:\n50 98 2", seeds.join(" "));
+            let greenhouse = Greenhouse::from(text.as_str());
+            prop_assert_eq!(greenhouse.to_string(), text);
+
+            // every range holds exactly `length` seeds, starting with `start`
+            prop_assert_eq!(greenhouse.seed_ranges.len(), pairs.len());
+            for (range, &(start, length)) in greenhouse.seed_ranges.iter().zip(&pairs) {
+                prop_assert_eq!(*range.start(), start);
+                prop_assert_eq!(range.clone().count() as u64, length);
+            }
+        }
+    }
+}
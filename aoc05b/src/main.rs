@@ -1,169 +1,16 @@
-use std::collections::{BTreeMap};
 use std::fs::read_to_string;
-use std::ops::RangeInclusive;
-use itertools::Itertools;
-
-fn main() {
-    /*
-    This solution works for example input,
-     but does not finish within any acceptable time for puzzle input
-     */
-    let greenhouse: Greenhouse =
-        Greenhouse::from(read_to_string("src/example").unwrap().trim());
-
-    let mut lowest: u64 = u64::MAX;
-
-    for seed_range in greenhouse.seed_ranges {
-        for seed in seed_range {
-            let soil = greenhouse.maps[&0].get_destination(seed);
-            let fertilizer = greenhouse.maps[&1].get_destination(soil);
-            let water = greenhouse.maps[&2].get_destination(fertilizer);
-            let light = greenhouse.maps[&3].get_destination(water);
-            let temperature = greenhouse.maps[&4].get_destination(light);
-            let humidity = greenhouse.maps[&5].get_destination(temperature);
-            let location = greenhouse.maps[&6].get_destination(humidity);
-            // dbg!(seed, soil, fertilizer,water, light, temperature, humidity, location);
-
-            lowest = lowest.min(location);
-        }
-    }
-
-    println!("{}", lowest);
-}
-
-#[derive(Debug)]
-struct Greenhouse {
-    seed_ranges: Vec<RangeInclusive<u64>>,
-    maps: BTreeMap<usize, Map>, // using BtreeMap to preserve (and nicely show) order
-}
-
-impl Greenhouse {
-    fn from(s: &str) -> Greenhouse {
-        //                   vvvv
-        // seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n [...]
-        // ^^^^^^^^^^^^^^^^^^    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-        // split_seeds           split_maps
-        let (split_seeds, split_maps) = s.split_once("\n\n").unwrap();
-
-        //      vv
-        // seeds: 79 14 55 13
-        // ^^^^^  ^^^^^^^^^^^
-        // _      split_seeds_numbers
-        let (_, split_seeds_numbers) = split_seeds.split_once(": ").unwrap();
-
-        //      v
-        // 79 14 55 13
-        // ^^^^^ ^^^^^
-        // |     seed_range[1]
-        // seed_range[0]
-        // (line can contain many more seeds than just two pairs)
-        // Given are seed range start and the length of the range.
-        // The end (inclusive) therefore is start + length
-        let seed_ranges: Vec<RangeInclusive<u64>> = split_seeds_numbers
-            .split_whitespace()
-            .map(|number_str| number_str.parse::<u64>().unwrap())
-            .tuples::<(u64, u64)>()
-            .map(|(start, length)|
-                // Range start, Range end (start plus length)
-                RangeInclusive::new(start, start + length))
-            .collect();
-
-        //                                     vvvv
-        // seed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n [...]
-        // ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
-        // split_maps_maps[0]                      split_maps_maps[1]
-        // ...split_maps_maps[n]
-        let split_maps_maps: Vec<&str> = split_maps.split("\n\n").collect();
-
-        let mut maps: BTreeMap<usize, Map> = BTreeMap::new();
-        for (i, split_maps_map) in split_maps_maps.into_iter().enumerate() {
-            maps.insert(i, Map::from(split_maps_map));
-        }
-
-        Greenhouse { seed_ranges, maps }
-    }
-}
 
+use aoc05b::{lowest_location, Greenhouse};
+use report::{input_hash, options_or_exit, timed, Report};
 
-#[derive(Debug)]
-struct Map {
-    ranges: Vec<Range>,
-}
-
-impl Map {
-    fn from(s: &str) -> Map {
-        //                 vvv
-        // seed-to-soil map:\n50 98 2\n52 50 48
-        // ^^^^^^^^^^^^^^^^   ^^^^^^^^^^^^^^^^^
-        // _         split_ranges
-        let (_, split_ranges) = s.split_once(":\n").unwrap();
-
-
-        //        vv
-        // 50 98 2\n52 50 48
-        // ^^^^^^^  ^^^^^^^^
-        // range[0] range[n]
-        let mut ranges: Vec<Range> = Vec::new();
-        for range in split_ranges.split("\n") {
-            ranges.push(Range::from(range))
-        }
-
-        Self { ranges }
-    }
-
-    fn get_destination(&self, source: u64) -> u64 {
-        // Check which range applies (if any)
-        match self.find_range_id(source) {
-            // If a Range can handle the mapping, let the Range figure out the destination
-            Some(id) => self.ranges[id].get_destination(source),
-            // If no Range can handle the mapping, output is input
-            None => source
-        }
-    }
-
-    fn find_range_id(&self, source: u64) -> Option<usize> {
-        self.ranges.iter()
-            .enumerate()
-            .find(|(_, x)| x.applies(source))
-            .map(|(id, _)| id)
-    }
-}
-
-
-#[derive(Debug)]
-struct Range {
-    destination_start: u64,
-    source_start: u64,
-    length: u64,
-}
-
-impl Range {
-    fn from(s: &str) -> Self {
-        // 50 98 2
-        // ^^ ^^ ^
-        // |  |  length
-        // |  source_start
-        // destination_start
-        let mut split = s.split_whitespace();
-        let destination_start: u64 = split.next().unwrap().parse().unwrap();
-        let source_start: u64 = split.next().unwrap().parse().unwrap();
-        let length: u64 = split.next().unwrap().parse().unwrap();
-        // not happy...
-
-        Range { destination_start, source_start, length }
-    }
-
-    fn applies(&self, source: u64) -> bool {
-        // A Range applies iff the source is within the range
-        // A Range is defined by its start and end (inclusive)
-
-        let start = self.source_start;
-        let end = self.source_start + self.length;
+fn main() {
+    // Usage: aoc05b [--format text|json] [[--input] <input>]
+    let options = options_or_exit();
+    let input = read_to_string(options.input_or_first("src/example")).unwrap();
 
-        start <= source && source <= end
-    }
+    let (greenhouse, parse_time) = timed(|| Greenhouse::from(input.trim()));
+    let (lowest, solve_time) = timed(|| lowest_location(&greenhouse));
 
-    fn get_destination(&self, source: u64) -> u64 {
-        source - self.source_start + self.destination_start
-    }
+    Report { day: 5, part: 'b', answer: lowest.to_string(), parse_time, solve_time, input_hash: input_hash(input.as_bytes()) }
+        .print(options.format);
 }
@@ -0,0 +1,25 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc07a::generator::generate;
+use aoc07a::{parse_hands, total_winnings};
+
+fn phases(c: &mut Criterion) {
+    let mut hands = Vec::new();
+    generate(&mut hands, 0x2023_1207, 10_000).unwrap();
+    let hands = String::from_utf8(hands).unwrap();
+
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("aoc07a 10000 hands", |b| b.iter(|| parse_hands(black_box(&hands))));
+    group.finish();
+
+    let hands = parse_hands(&hands);
+
+    let mut group = c.benchmark_group("solve");
+    group.bench_function("aoc07a 10000 hands", |b| b.iter(|| total_winnings(black_box(&hands))));
+    group.finish();
+}
+
+criterion_group!(benches, phases);
+criterion_main!(benches);
@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+static LABELS: &[u8] = b"23456789TJQKA";
+// the order of the labels in part 2, where J is a joker
+static JOKER_LABELS: &[u8] = b"J23456789TQKA";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    // total winnings
+    pub part1: u64,
+    // total winnings, with J as jokers
+    pub part2: u64,
+}
+
+impl seeded::Answers for Answers {
+    fn parts(&self) -> Vec<u128> { vec![self.part1.into(), self.part2.into()] }
+}
+
+pub fn generate<W: Write>(mut writer: W, seed: u64, hands: usize) -> io::Result<Answers> {
+    // Hands like the puzzle input: all different, with bids from 1 to 1000.
+    // Each hand draws its cards from a few labels only, so all types of hands come up.
+    //
+    // The answers rank the hands by counting their labels, not by looking for the types one after another.
+    assert!(hands <= LABELS.len().pow(5));
+
+    let mut random = seeded::random(seed);
+
+    let mut seen = HashSet::new();
+    let mut drawn: Vec<([u8; 5], u64)> = Vec::with_capacity(hands);
+    while drawn.len() < hands {
+        let labels: Vec<u8> = (0..1 + random(5)).map(|_| LABELS[random(LABELS.len())]).collect();
+        let cards: [u8; 5] = std::array::from_fn(|_| labels[random(labels.len())]);
+        if !seen.insert(cards) { continue; }
+
+        let bid = 1 + random(1000) as u64;
+        writeln!(writer, "{} {bid}", String::from_utf8_lossy(&cards))?;
+        drawn.push((cards, bid));
+    }
+
+    let part1 = winnings(&drawn, LABELS, false);
+    let part2 = winnings(&drawn, JOKER_LABELS, true);
+
+    writer.flush()?;
+    Ok(Answers { part1, part2 })
+}
+
+fn winnings(hands: &[([u8; 5], u64)], labels: &[u8], jokers: bool) -> u64 {
+    // A hand's type follows from how often its labels occur, most often first:
+    // [5] five of a kind, [4, 1] four of a kind, [3, 2] full house, ... [1, 1, 1, 1, 1] high card.
+    // Jokers join the label that occurs most often.
+    let key = |cards: &[u8; 5]| {
+        let mut counts = vec![0; labels.len()];
+        for card in cards { counts[labels.iter().position(|label| label == card).unwrap()] += 1; }
+
+        let joined = if jokers { std::mem::take(&mut counts[0]) } else { 0 };
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        counts[0] += joined;
+
+        let values: Vec<usize> = cards.iter().map(|card| labels.iter().position(|label| label == card).unwrap()).collect();
+        (counts[0], counts[1], values)
+    };
+
+    let mut ranked: Vec<_> = hands.iter().map(|(cards, bid)| (key(cards), *bid)).collect();
+    ranked.sort_unstable();
+
+    ranked.iter().zip(1..).map(|((_, bid), rank)| bid * rank).sum()
+}
+
+
+#[cfg(test)]
+mod synthetic {
+    use std::collections::HashSet;
+
+    use crate::generator::generate;
+    use crate::{parse_hands, total_winnings};
+
+    #[test]
+    fn generate_answers() {
+        let mut hands = Vec::new();
+        let answers = generate(&mut hands, 42, 1000).unwrap();
+
+        let hands = parse_hands(&String::from_utf8(hands).unwrap());
+        assert_eq!(hands.len(), 1000);
+        assert_eq!(total_winnings(&hands), answers.part1);
+
+        // all different hands, so their ranks are clear, and all types of hands among them
+        assert_eq!(hands.iter().map(|hand| &hand.cards).collect::<HashSet<_>>().len(), 1000);
+        assert_eq!(hands.iter().map(|hand| hand.strength).collect::<HashSet<_>>().len(), 7);
+    }
+}
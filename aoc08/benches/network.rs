@@ -0,0 +1,47 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc08::parse_input;
+
+fn generate(seed: u64) -> String {
+    // Every possible three letter label (26^3 = 17576 nodes),
+    // connected at random, walked by 283 random instructions.
+    let mut random = seeded::random(seed);
+
+    let mut labels: Vec<String> = Vec::new();
+    for a in 'A'..='Z' {
+        for b in 'A'..='Z' {
+            for c in 'A'..='Z' { labels.push(format!("{a}{b}{c}")) }
+        }
+    }
+
+    let mut s: String = (0..283).map(|_| if random(2) == 0 { 'L' } else { 'R' }).collect();
+    s.push_str("\n\n");
+
+    for label in &labels {
+        let left = &labels[random(labels.len())];
+        let right = &labels[random(labels.len())];
+        s.push_str(&format!("{label} = ({left}, {right})\n"));
+    }
+
+    s
+}
+
+fn network(c: &mut Criterion) {
+    let input = generate(0x2023_1208);
+
+    c.bench_function("parse 17576 nodes", |b| b.iter(|| parse_input(black_box(&input)).unwrap()));
+
+    let (instructions, network) = parse_input(&input).unwrap();
+    let start = network.id("AAA").unwrap();
+
+    c.bench_function("walk 1000000 steps", |b| b.iter(|| {
+        network.walk(black_box(start), &instructions)
+            .take(1_000_000)
+            .last()
+    }));
+}
+
+criterion_group!(benches, network);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc08::cycle::{solve, Cycle};
+use aoc08::generator::generate;
+use aoc08::parse_input;
+
+fn phases(c: &mut Criterion) {
+    // sized like the puzzle input: 6 ghosts, 281 instructions, cycles of some ten thousand steps
+    let mut document = Vec::new();
+    generate(&mut document, 0x2023_1208, 281, &[43, 47, 59, 61, 67, 73]).unwrap();
+    let document = String::from_utf8(document).unwrap();
+
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("aoc08 6 ghosts", |b| b.iter(|| parse_input(black_box(&document)).unwrap()));
+    group.finish();
+
+    let (instructions, network) = parse_input(&document).unwrap();
+    let start = network.id("AAA").unwrap();
+    let end = network.id("ZZZ").unwrap();
+
+    let mut group = c.benchmark_group("solve");
+    group.bench_function("aoc08a 6 ghosts", |b| b.iter(|| network.steps(black_box(start), end, &instructions).unwrap()));
+    group.bench_function("aoc08b 6 ghosts", |b| b.iter(|| {
+        let cycles: Vec<Cycle> = network.nodes_start().into_iter()
+            .map(|start| network.cycle(start, black_box(&instructions)))
+            .collect();
+        solve(&cycles).unwrap().unwrap()
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, phases);
+criterion_main!(benches);
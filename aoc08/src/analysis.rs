@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::{Direction, Network, NodeId};
+
+impl Network {
+    pub fn path(&self, start: NodeId, instructions: &[Direction]) -> Vec<(NodeId, Direction)> {
+        // Every node (and the direction it is left in) visited when following the instructions,
+        // until a state (node and position within the instructions) repeats.
+        // Everything that comes after that is just a repetition of the path.
+        let mut seen: HashSet<(NodeId, usize)> = HashSet::new();
+        let mut path = Vec::new();
+
+        for (step, current) in self.walk(start, instructions).enumerate() {
+            let position = step % instructions.len();
+            if !seen.insert((current, position)) { break; }
+            path.push((current, instructions[position]));
+        }
+
+        path
+    }
+
+    pub fn reachable_ends(&self, start: NodeId, instructions: &[Direction]) -> Vec<NodeId> {
+        // End nodes in order of their first visit
+        let mut ends = Vec::new();
+        for (node, _) in self.path(start, instructions) {
+            if self.is_end_node(node) && !ends.contains(&node) { ends.push(node); }
+        }
+        ends
+    }
+
+    pub fn unreachable_nodes(&self, instructions: &[Direction]) -> Vec<NodeId> {
+        // Nodes never visited by any start node following the instructions
+        let visited: HashSet<NodeId> = self.nodes_start().into_iter()
+            .flat_map(|start| self.path(start, instructions))
+            .map(|(node, _)| node)
+            .collect();
+
+        self.ids()
+            .filter(|id| !visited.contains(id))
+            .collect()
+    }
+
+    pub fn dead_ends(&self, instructions: &[Direction]) -> Vec<NodeId> {
+        // Nodes from which following the instructions never hits an end node:
+        // the walk loops forever without reaching one.
+        self.ids()
+            .filter(|&id| self.reachable_ends(id, instructions).is_empty())
+            .collect()
+    }
+
+    pub fn to_dot(&self, instructions: &[Direction]) -> String {
+        // GraphViz representation of the network, render with e.g. `dot -Tsvg network.dot`
+        // - start nodes are filled, end nodes are double circles
+        // - edges taken when following the instructions from the start nodes are highlighted
+        // - nodes never visited that way are greyed out
+        let taken: HashSet<(NodeId, Direction)> = self.nodes_start().into_iter()
+            .flat_map(|start| self.path(start, instructions))
+            .collect();
+        let unreachable: HashSet<NodeId> = self.unreachable_nodes(instructions).into_iter().collect();
+
+        let quote = |id: NodeId| format!("\"{}\"", self.label(id).replace('"', "\\\""));
+
+        let mut dot = String::from("digraph network {\n");
+
+        for id in self.ids() {
+            let mut attributes = Vec::new();
+            if self.label(id).ends_with('A') { attributes.push("style=filled, fillcolor=lightblue"); }
+            if self.is_end_node(id) { attributes.push("shape=doublecircle"); }
+            if unreachable.contains(&id) { attributes.push("color=grey, fontcolor=grey"); }
+
+            if attributes.is_empty() {
+                writeln!(dot, "    {};", quote(id)).unwrap();
+            } else {
+                writeln!(dot, "    {} [{}];", quote(id), attributes.join(", ")).unwrap();
+            }
+        }
+
+        for id in self.ids() {
+            for (direction, label) in [(Direction::Left, "L"), (Direction::Right, "R")] {
+                let highlight = if taken.contains(&(id, direction)) { ", color=red, penwidth=2" } else { "" };
+                writeln!(dot, "    {} -> {} [label=\"{}\"{}];",
+                         quote(id), quote(self.next(id, direction)), label, highlight).unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+
+#[cfg(test)]
+mod reachability {
+    use crate::{parse_input, Direction, Network, NodeId};
+
+    fn example() -> (Vec<Direction>, Network) {
+        parse_input("LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+33A = (33Z, 33Z)
+33Z = (XXX, XXX)").unwrap()
+    }
+
+    fn labels(network: &Network, ids: Vec<NodeId>) -> Vec<&str> {
+        ids.into_iter().map(|id| network.label(id)).collect()
+    }
+
+    #[test]
+    fn reachable_ends() {
+        let (instructions, network) = example();
+
+        assert_eq!(labels(&network, network.reachable_ends(network.id("11A").unwrap(), &instructions)), vec!["11Z"]);
+        assert_eq!(labels(&network, network.reachable_ends(network.id("22A").unwrap(), &instructions)), vec!["22Z"]);
+        assert_eq!(labels(&network, network.reachable_ends(network.id("33A").unwrap(), &instructions)), vec!["33Z"]);
+        assert_eq!(labels(&network, network.reachable_ends(network.id("XXX").unwrap(), &instructions)), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn unreachable_nodes() {
+        let (instructions, network) = example();
+
+        // only 33A runs into XXX, but that is enough to reach it
+        assert_eq!(labels(&network, network.unreachable_nodes(&instructions)), Vec::<&str>::new());
+
+        let (_, network) = parse_input("L\n\nAAA = (ZZZ, BBB)\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert_eq!(labels(&network, network.unreachable_nodes(&[Direction::Left])), vec!["BBB"]);
+    }
+
+    #[test]
+    fn dead_ends() {
+        let (instructions, network) = example();
+
+        // 11B could reach 11Z going right, but the walk from it starts going left
+        assert_eq!(labels(&network, network.dead_ends(&instructions)), vec!["11B", "XXX"]);
+    }
+
+    #[test]
+    fn dead_ends_follow_the_instructions() {
+        // every node has an edge to ZZZ, but always going left AAA and BBB only visit each other
+        let (_, network) = parse_input("L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+
+        assert_eq!(labels(&network, network.dead_ends(&[Direction::Left])), vec!["AAA", "BBB"]);
+        assert_eq!(labels(&network, network.dead_ends(&[Direction::Right])), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn to_dot() {
+        let (_, network) = parse_input("L\n\nAAA = (ZZZ, BBB)\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+
+        assert_eq!(network.to_dot(&[Direction::Left]), "digraph network {
+    \"AAA\" [style=filled, fillcolor=lightblue];
+    \"BBB\" [color=grey, fontcolor=grey];
+    \"ZZZ\" [shape=doublecircle];
+    \"AAA\" -> \"ZZZ\" [label=\"L\", color=red, penwidth=2];
+    \"AAA\" -> \"BBB\" [label=\"R\"];
+    \"BBB\" -> \"ZZZ\" [label=\"L\"];
+    \"BBB\" -> \"ZZZ\" [label=\"R\"];
+    \"ZZZ\" -> \"ZZZ\" [label=\"L\", color=red, penwidth=2];
+    \"ZZZ\" -> \"ZZZ\" [label=\"R\"];
+}
+");
+    }
+}
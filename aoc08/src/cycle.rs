@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{Direction, Network, NodeId};
+
+impl Network {
+    pub fn cycle(&self, start: NodeId, instructions: &[Direction]) -> Cycle {
+        // Walks from the start node until a state repeats.
+        // A state is the current node together with the position within the instructions,
+        // as the same node can be left in another direction when visited at another position.
+        // Once a state repeats, the walk is caught in a cycle forever.
+        let mut seen: HashMap<(NodeId, usize), u64> = HashMap::new();
+        let mut hits: Vec<u64> = Vec::new();
+
+        for (step, current) in (0..).zip(self.walk(start, instructions)) {
+            let position = (step % instructions.len() as u64) as usize;
+
+            if let Some(&offset) = seen.get(&(current, position)) {
+                let (prefix_hits, cycle_hits) = hits.into_iter()
+                    .partition(|&hit| hit < offset);
+
+                return Cycle { offset, length: step - offset, prefix_hits, cycle_hits };
+            }
+
+            seen.insert((current, position), step);
+            if self.is_end_node(current) { hits.push(step); }
+        }
+
+        unreachable!()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Cycle {
+    // Walking from a start node, the first `offset` steps lead into a cycle of `length` steps,
+    // which then repeats forever.
+    // End nodes before the cycle are reached only once (prefix_hits),
+    // end nodes within the cycle are reached again every `length` steps (cycle_hits).
+    pub offset: u64,
+    pub length: u64,
+    pub prefix_hits: Vec<u64>,
+    pub cycle_hits: Vec<u64>,
+}
+
+impl Cycle {
+    pub fn hits(&self, step: u64) -> bool {
+        self.prefix_hits.contains(&step)
+            || self.cycle_hits.iter()
+            .any(|&hit| step >= hit && (step - hit).is_multiple_of(self.length))
+    }
+}
+
+pub fn solve(cycles: &[Cycle]) -> Result<Option<u64>, Overflow> {
+    // All ghosts stand on end nodes at the same time,
+    // iff that step is a hit for every single ghost.
+    // Ok(None) if that never happens, Overflow if the step, or the cycles combined on the way to it, do not fit in u64.
+    if cycles.is_empty() { return Ok(None); }
+
+    // With the puzzle input, every ghost reaches exactly one end node,
+    // and it does so exactly at the end of each cycle.
+    // The first common step then simply is the least common multiple of all cycle lengths.
+    if cycles.iter().all(|c| c.prefix_hits.is_empty() && c.cycle_hits == [c.length]) {
+        return cycles.iter().try_fold(1, |multiple, c| lcm(multiple, c.length)).map(Some).ok_or(Overflow);
+    }
+
+    // Otherwise, fall back to the general solution.
+    // Hits before entering a cycle only happen once, so they can be checked one by one.
+    let first_prefix = cycles.iter()
+        .flat_map(|c| c.prefix_hits.iter().copied())
+        .filter(|&step| cycles.iter().all(|c| c.hits(step)))
+        .min();
+
+    // Every combination of hits within the cycles forms a system of congruences
+    // (step = hit mod length), which is solved by the chinese remainder theorem.
+    // The step must not be earlier than any of the chosen hits.
+    let mut congruences: Vec<(u64, u64, u64)> = vec![(0, 1, 0)]; // (remainder, modulus, earliest)
+    for cycle in cycles {
+        let mut next = Vec::new();
+        for &(remainder, modulus, earliest) in &congruences {
+            for &hit in &cycle.cycle_hits {
+                if let Some((r, m)) = crt((remainder, modulus), (hit % cycle.length, cycle.length))? {
+                    next.push((r, m, earliest.max(hit)));
+                }
+            }
+        }
+        congruences = next;
+    }
+
+    // A step past u64::MAX only matters if there is no other.
+    let steps: Vec<Option<u64>> = congruences.into_iter()
+        .map(|(remainder, modulus, earliest)| {
+            if remainder >= earliest {
+                Some(remainder)
+            } else {
+                (earliest - remainder).div_ceil(modulus).checked_mul(modulus)?.checked_add(remainder)
+            }
+        })
+        .collect();
+    let first_cycle = steps.iter().flatten().copied().min();
+
+    match (first_prefix, first_cycle) {
+        (Some(p), Some(c)) => Ok(Some(p.min(c))),
+        (None, None) if !steps.is_empty() => Err(Overflow),
+        (p, c) => Ok(p.or(c)),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the steps until the ghosts meet on end nodes do not fit in 64 bits")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+fn crt((r1, m1): (u64, u64), (r2, m2): (u64, u64)) -> Result<Option<(u64, u64)>, Overflow> {
+    // Merges step = r1 (mod m1) and step = r2 (mod m2) into one congruence step = r (mod m).
+    // For moduli that are not coprime, a solution only exists,
+    // iff both remainders agree modulo gcd(m1, m2).
+    // The merged modulus has to fit in u64, then so does m1 * k below.
+    let m = lcm(m1, m2).ok_or(Overflow)?;
+    let g = gcd(m1, m2) as i128;
+    let (r1, m1, r2, m2) = (r1 as i128, m1 as i128, r2 as i128, m2 as i128);
+
+    if (r2 - r1) % g != 0 { return Ok(None); }
+
+    let (_, inverse, _) = extended_gcd(m1 / g, m2 / g);
+    let n = m2 / g;
+    let k = (((r2 - r1) / g).rem_euclid(n) as u128 * inverse.rem_euclid(n) as u128 % n as u128) as i128;
+    let r = (r1 + m1 * k).rem_euclid(m as i128);
+
+    Ok(Some((u64::try_from(r).map_err(|_| Overflow)?, m)))
+}
+
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    // returns (gcd, x, y) with a * x + b * y = gcd
+    if b == 0 { return (a, 1, 0); }
+    let (g, x, y) = extended_gcd(b, a % b);
+    (g, y, x - a / b * y)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+fn lcm(a: u64, b: u64) -> Option<u64> {
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+
+#[cfg(test)]
+mod ghosts {
+    use crate::parse_input;
+    use crate::cycle::{crt, solve, Cycle, Overflow};
+
+    #[test]
+    fn cycle() {
+        let (instructions, network) = parse_input("LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)").unwrap();
+
+        // 11A -> 11B -> 11Z -> 11B -> 11Z ...
+        assert_eq!(network.cycle(network.id("11A").unwrap(), &instructions),
+                   Cycle { offset: 1, length: 2, prefix_hits: vec![], cycle_hits: vec![2] });
+
+        // 22A -> 22B -> 22C -> 22Z -> 22B -> 22C -> 22Z ...
+        assert_eq!(network.cycle(network.id("22A").unwrap(), &instructions),
+                   Cycle { offset: 1, length: 6, prefix_hits: vec![], cycle_hits: vec![3, 6] });
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt((2, 3), (3, 5)), Ok(Some((8, 15))));
+        assert_eq!(crt((0, 4), (2, 6)), Ok(Some((8, 12))));
+        assert_eq!(crt((1, 4), (2, 6)), Ok(None));
+        assert_eq!(crt((1, u64::MAX - 1), (2, u64::MAX)), Err(Overflow));
+    }
+
+    #[test]
+    fn overflow() {
+        // coprime cycle lengths just above 2^32, meeting only after more than 2^64 steps
+        let cycle = |length: u64, hit: u64| Cycle { offset: 1, length, prefix_hits: vec![], cycle_hits: vec![hit] };
+        assert_eq!(solve(&[cycle(4294967311, 4294967311), cycle(4294967357, 4294967357)]), Err(Overflow));
+        assert_eq!(solve(&[cycle(4294967311, 5), cycle(4294967357, 7)]), Err(Overflow));
+        assert_eq!(solve(&[cycle(4294967311, 4294967311), cycle(2, 2)]), Ok(Some(2 * 4294967311)));
+    }
+}
@@ -0,0 +1,158 @@
+use std::io::{self, Write};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    // steps from AAA to ZZZ
+    pub part1: u64,
+    // steps until all ghosts stand on end nodes
+    pub part2: u64,
+}
+
+impl seeded::Answers for Answers {
+    fn parts(&self) -> Vec<u128> { vec![self.part1.into(), self.part2.into()] }
+}
+
+pub fn generate<W: Write>(mut writer: W, seed: u64, instructions: usize, cycles: &[u64]) -> io::Result<Answers> {
+    // A network like the puzzle input: every ghost walks a ring of its own,
+    // from its start node to its end node in cycle × instructions steps,
+    // and from there around the ring again (the end node leads where the start node does).
+    // Along the ring, only the direction of the instruction taken there leads on,
+    // the other direction leads anywhere, as do the nodes no ghost ever reaches.
+    // The first ghost starts at AAA and ends at ZZZ.
+    //
+    // The answers follow from the rings: the first ghost needs its cycle length to reach ZZZ,
+    // and all ghosts meet on end nodes at the least common multiple of the cycle lengths.
+    assert!(instructions >= 1 && !cycles.is_empty() && cycles.len() < 26 * 26 - 1);
+    assert!(cycles.iter().all(|&cycle| cycle >= 1));
+
+    let mut random = seeded::random(seed);
+
+    let directions: Vec<usize> = (0..instructions).map(|_| random(2)).collect();
+    let lengths: Vec<u64> = cycles.iter().map(|&cycle| cycle * instructions as u64).collect();
+
+    // ring nodes first, ghost by ghost, then the nodes no ghost reaches
+    let ring_nodes: usize = lengths.iter().map(|&length| length as usize + 1).sum();
+    let total = ring_nodes + ring_nodes / 4;
+
+    let mut labels: Vec<String> = Vec::with_capacity(total);
+    let mut next: Vec<[usize; 2]> = Vec::with_capacity(total);
+
+    for (ghost, &length) in lengths.iter().enumerate() {
+        // start, ring nodes in between, end
+        let (start, end) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => (format!("{}A", label(ghost, 2)), format!("{}Z", label(ghost, 2))),
+        };
+
+        let first = labels.len();
+        for i in 0..=length as usize {
+            labels.push(match i {
+                0 => start.clone(),
+                i if i == length as usize => end.clone(),
+                _ => inner(labels.len()),
+            });
+
+            // the node reached after i steps takes the instruction at i
+            let onwards = if i == length as usize { first + 1 } else { labels.len() };
+            let mut edges = [random(total), random(total)];
+            edges[directions[i % instructions]] = onwards;
+            next.push(edges);
+        }
+    }
+
+    while labels.len() < total {
+        labels.push(inner(labels.len()));
+        next.push([random(total), random(total)]);
+    }
+
+    let instruction_line: String = directions.iter().map(|&direction| if direction == 0 { 'L' } else { 'R' }).collect();
+    write!(writer, "{instruction_line}\n\n")?;
+
+    let mut order: Vec<usize> = (0..total).collect();
+    for i in 0..order.len() {
+        let j = i + random(order.len() - i);
+        order.swap(i, j);
+    }
+    for (n, &i) in order.iter().enumerate() {
+        let [left, right] = next[i];
+        write!(writer, "{} = ({}, {})", labels[i], labels[left], labels[right])?;
+        if n + 1 < total { writeln!(writer)?; }
+    }
+
+    let part1 = lengths[0];
+    let part2 = lengths.iter().fold(1, |lcm, &length| {
+        lcm / gcd(lcm, length) * length
+    });
+
+    writer.flush()?;
+    Ok(Answers { part1, part2 })
+}
+
+fn label(mut n: usize, min_length: usize) -> String {
+    // base 26, most significant letter first
+    let mut letters = Vec::new();
+    while n > 0 || letters.len() < min_length {
+        letters.push(b'A' + (n % 26) as u8);
+        n /= 26;
+    }
+    letters.reverse();
+    String::from_utf8(letters).unwrap()
+}
+
+fn inner(n: usize) -> String {
+    // the last letter is neither A nor Z, so the node neither starts nor ends a walk
+    format!("{}{}", label(n / 24, 2), (b'B' + (n % 24) as u8) as char)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+
+#[cfg(test)]
+mod synthetic {
+    use crate::cycle::{solve, Cycle};
+    use crate::generator::generate;
+    use crate::parse_input;
+
+    #[test]
+    fn generate_answers() {
+        let mut network = Vec::new();
+        let answers = generate(&mut network, 42, 53, &[43, 47, 59, 61, 67, 71]).unwrap();
+        assert_eq!(answers.part1, 43 * 53);
+        assert_eq!(answers.part2, 43 * 47 * 53 * 59 * 61 * 67 * 71);
+
+
+        let (instructions, network) = parse_input(&String::from_utf8(network).unwrap()).unwrap();
+        assert_eq!(instructions.len(), 53);
+        assert_eq!(network.nodes_start().len(), 6);
+
+        let start = network.id("AAA").unwrap();
+        let end = network.id("ZZZ").unwrap();
+        assert_eq!(network.steps(start, end, &instructions), Ok(answers.part1));
+
+        let cycles: Vec<Cycle> = network.nodes_start().into_iter()
+            .map(|start| network.cycle(start, &instructions))
+            .collect();
+        // every ghost walks its ring from the start, and meets its end once per cycle
+        assert!(cycles.iter().all(|cycle| cycle.offset == 1 && cycle.cycle_hits == vec![cycle.length]));
+        let mut lengths: Vec<u64> = cycles.iter().map(|cycle| cycle.length / 53).collect();
+        lengths.sort();
+        assert_eq!(lengths, vec![43, 47, 59, 61, 67, 71]);
+        assert_eq!(solve(&cycles), Ok(Some(answers.part2)));
+    }
+
+    #[test]
+    fn shared_cycle_lengths() {
+        // ghosts with the same cycle meet after one cycle, 2 and 4 only once both finish 4
+        let mut network = Vec::new();
+        let answers = generate(&mut network, 7, 5, &[2, 4, 2]).unwrap();
+        assert_eq!(answers, crate::generator::Answers { part1: 10, part2: 20 });
+
+        let (instructions, network) = parse_input(&String::from_utf8(network).unwrap()).unwrap();
+        let cycles: Vec<Cycle> = network.nodes_start().into_iter()
+            .map(|start| network.cycle(start, &instructions))
+            .collect();
+        assert_eq!(solve(&cycles), Ok(Some(20)));
+    }
+}
@@ -0,0 +1,553 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+pub mod analysis;
+pub mod cycle;
+pub mod generator;
+
+pub type NodeId = u32;
+
+pub fn parse_input(s: &str) -> Result<(Vec<Direction>, Network), Vec<ParseError>> {
+    // LLR                 <- instructions, the first non-empty line
+    //
+    // AAA = (BBB, BBB)    <- nodes, every further non-empty line
+    // BBB = (AAA, ZZZ)
+    //
+    // Lines are numbered as in the input, to point errors to the right line.
+    let mut lines = s.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    let (line, input_instructions) = lines.next()
+        .ok_or(vec![ParseError::MissingInstructions])?;
+
+    let instructions = input_instructions.chars()
+        .map(|c| Direction::try_from(c)
+            .map_err(|_| ParseError::InvalidInstruction { line, instruction: c }))
+        .collect::<Result<_, _>>()
+        .map_err(|e| vec![e])?;
+
+    let network = Network::from_lines(lines)?;
+
+    Ok((instructions, network))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingInstructions,
+    InvalidInstruction { line: usize, instruction: char },
+    MalformedNode { line: usize, content: String },
+    DuplicateDefinition { line: usize, label: String, first_line: usize },
+    UnknownLabel { line: usize, label: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MissingInstructions =>
+                write!(f, "missing instructions"),
+            ParseError::InvalidInstruction { line, instruction } =>
+                write!(f, "line {line}: invalid instruction '{instruction}', expected 'L' or 'R'"),
+            ParseError::MalformedNode { line, content } =>
+                write!(f, "line {line}: expected 'AAA = (BBB, CCC)', got '{content}'"),
+            ParseError::DuplicateDefinition { line, label, first_line } =>
+                write!(f, "line {line}: node {label} is already defined in line {first_line}"),
+            ParseError::UnknownLabel { line, label } =>
+                write!(f, "line {line}: node {label} is never defined"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction { Left, Right }
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::Left => write!(f, "L"),
+            Direction::Right => write!(f, "R"),
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            c => Err(c)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Network {
+    // Every label is interned to a NodeId (its index) while parsing,
+    // so walking the network never needs to touch a String again.
+    labels: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    // edges[id] = [left, right]
+    edges: Vec<[NodeId; 2]>,
+    ends: Vec<bool>,
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // AAA = (BBB, CCC)    <- one line per node, in the order they were defined
+        // BBB = (DDD, EEE)
+        for id in self.ids() {
+            if id > 0 { writeln!(f)?; }
+            let [left, right] = self.edges[id as usize];
+            write!(f, "{} = ({}, {})", self.label(id), self.label(left), self.label(right))?;
+        }
+        Ok(())
+    }
+}
+
+impl Network {
+    fn new() -> Self {
+        Self {
+            labels: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+            ends: Vec::new(),
+        }
+    }
+
+    fn from_lines<'a>(lines: impl Iterator<Item=(usize, &'a str)>) -> Result<Self, Vec<ParseError>> {
+        let mut network = Self::new();
+        let mut errors = Vec::new();
+
+        // first pass: give every defined label its id,
+        // so references to nodes defined further down can be resolved
+        let mut nodes: Vec<(usize, Node)> = Vec::new();
+        for (line, content) in lines {
+            let Some(node) = Node::from(content) else {
+                errors.push(ParseError::MalformedNode { line, content: content.to_string() });
+                continue;
+            };
+
+            match network.id(node.name) {
+                Some(id) => errors.push(ParseError::DuplicateDefinition {
+                    line,
+                    label: node.name.to_string(),
+                    first_line: nodes[id as usize].0,
+                }),
+                None => {
+                    network.intern(node.name);
+                    nodes.push((line, node));
+                }
+            }
+        }
+
+        // second pass: connect the nodes
+        for (line, node) in &nodes {
+            let mut resolve = |label: &str| network.id(label).unwrap_or_else(|| {
+                errors.push(ParseError::UnknownLabel { line: *line, label: label.to_string() });
+                0
+            });
+
+            let left = resolve(node.left);
+            let right = resolve(node.right);
+            network.edges.push([left, right]);
+        }
+
+        if errors.is_empty() { Ok(network) } else { Err(errors) }
+    }
+
+    fn intern(&mut self, label: &str) -> NodeId {
+        let id = self.labels.len() as NodeId;
+        self.labels.push(label.to_string());
+        self.ids.insert(label.to_string(), id);
+        self.ends.push(label.ends_with('Z'));
+        id
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item=NodeId> {
+        0..self.len() as NodeId
+    }
+
+    pub fn id(&self, label: &str) -> Option<NodeId> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: NodeId) -> &str {
+        &self.labels[id as usize]
+    }
+
+    pub fn next(&self, id: NodeId, direction: Direction) -> NodeId {
+        self.edges[id as usize][direction as usize]
+    }
+
+    pub fn is_end_node(&self, id: NodeId) -> bool {
+        self.ends[id as usize]
+    }
+
+    pub fn nodes_start(&self) -> Vec<NodeId> {
+        self.ids()
+            .filter(|&id| self.label(id).ends_with('A'))
+            .collect()
+    }
+
+    pub fn steps(&self, start: NodeId, target: NodeId, instructions: &[Direction]) -> Result<u64, Unreachable> {
+        // Follows the instructions until the target is reached.
+        // If a state (node and position within the instructions) repeats before that,
+        // the walk is caught in a loop that will never lead to the target.
+        let mut seen: HashSet<(NodeId, usize)> = HashSet::new();
+
+        for (step, current) in (0..).zip(self.walk(start, instructions)) {
+            if current == target { return Ok(step); }
+
+            let position = (step % instructions.len() as u64) as usize;
+            if !seen.insert((current, position)) {
+                return Err(Unreachable {
+                    start: self.label(start).to_string(),
+                    target: self.label(target).to_string(),
+                    loop_after: step,
+                });
+            }
+        }
+
+        unreachable!()
+    }
+
+    pub fn walk<'a>(&'a self, start: NodeId, instructions: &'a [Direction]) -> Walk<'a> {
+        Walk { network: self, instructions, position: 0, current: Some(start) }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Unreachable {
+    pub start: String,
+    pub target: String,
+    // number of steps after which the walk was found to be looping
+    pub loop_after: u64,
+}
+
+impl fmt::Display for Unreachable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} can not be reached from {}, the walk loops after {} steps", self.target, self.start, self.loop_after)
+    }
+}
+
+impl std::error::Error for Unreachable {}
+
+pub struct Walk<'a> {
+    // Follows the instructions (over and over again) from a start node.
+    // Yields the start node first, so the n-th item is the node reached after n steps.
+    network: &'a Network,
+    instructions: &'a [Direction],
+    position: usize,
+    current: Option<NodeId>,
+}
+
+impl Iterator for Walk<'_> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current?;
+
+        let direction = self.instructions[self.position];
+        self.position = (self.position + 1) % self.instructions.len();
+        self.current = Some(self.network.next(current, direction));
+
+        Some(current)
+    }
+}
+
+#[derive(Debug)]
+struct Node<'a> {
+    name: &'a str,
+    left: &'a str,
+    right: &'a str,
+}
+
+impl<'a> Node<'a> {
+    fn from(node_str: &'a str) -> Option<Self> {
+        //     v
+        // AAA = (BBB, CCC)
+        // ^^^   ^^^^^^^^^^
+        // name  split_neighbours
+        let (name, split_neighbours) = node_str.split_once('=')?;
+
+        //      v
+        // (BBB, CCC)
+        //  ^^^  ^^^
+        //  left right
+        let (left, right) = split_neighbours.trim()
+            .strip_prefix('(')?
+            .strip_suffix(')')?
+            .split_once(',')?;
+
+        let (name, left, right) = (name.trim(), left.trim(), right.trim());
+
+        if [name, left, right].iter().all(|label| Node::is_label(label)) {
+            Some(Self { name, left, right })
+        } else {
+            None
+        }
+    }
+
+    fn is_label(label: &str) -> bool {
+        // Labels can be of any length,
+        // as long as they can not be confused with the rest of the line
+        !label.is_empty()
+            && !label.contains(|c: char| c.is_whitespace() || "=(),".contains(c))
+    }
+}
+
+
+#[cfg(test)]
+mod network {
+    use crate::{parse_input, Direction, Direction::*, ParseError, Unreachable};
+
+    static EXAMPLE: &str = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    #[test]
+    fn interning() {
+        let (instructions, network) = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!(instructions, vec![Right, Left]);
+        assert_eq!(network.len(), 7);
+
+        let aaa = network.id("AAA").unwrap();
+        assert_eq!(network.label(aaa), "AAA");
+        assert_eq!(network.label(network.next(aaa, Left)), "BBB");
+        assert_eq!(network.label(network.next(aaa, Right)), "CCC");
+
+        assert_eq!(network.id("XXX"), None);
+        assert_eq!(network.nodes_start(), vec![aaa]);
+        assert!(network.is_end_node(network.id("ZZZ").unwrap()));
+    }
+
+    #[test]
+    fn print() {
+        let (instructions, network) = parse_input(EXAMPLE).unwrap();
+        let instructions: String = instructions.iter().map(Direction::to_string).collect();
+
+        assert_eq!(format!("{instructions}\n\n{network}"), EXAMPLE);
+
+        let (_, spaced) = parse_input("L\n\n  START=(  LEFT,START )\r\nLEFT = (LEFT, LEFT)").unwrap();
+        assert_eq!(spaced.to_string(), "START = (LEFT, START)\nLEFT = (LEFT, LEFT)");
+        assert_eq!(parse_input(&format!("L\n\n{spaced}")).unwrap().1, spaced);
+    }
+
+    #[test]
+    fn walk() {
+        let (instructions, network) = parse_input(EXAMPLE).unwrap();
+
+        let labels: Vec<&str> = network.walk(network.id("AAA").unwrap(), &instructions)
+            .take(4)
+            .map(|id| network.label(id))
+            .collect();
+
+        assert_eq!(labels, vec!["AAA", "CCC", "ZZZ", "ZZZ"]);
+    }
+
+    #[test]
+    fn line_endings_and_spacing() {
+        let (instructions, network) = parse_input(
+            "\r\nLR\r\n\r\n  START=(  LEFT,RIGHT )  \r\nLEFT = (LEFT, LEFT)\r\n\r\nRIGHT =(START ,RIGHT)\r\n"
+        ).unwrap();
+
+        assert_eq!(instructions, vec![Left, Right]);
+        assert_eq!(network.len(), 3);
+
+        let start = network.id("START").unwrap();
+        assert_eq!(network.label(network.next(start, Left)), "LEFT");
+        assert_eq!(network.label(network.next(start, Right)), "RIGHT");
+    }
+
+    #[test]
+    fn label_lengths() {
+        let (_, network) = parse_input("L\n\nA = (BB, Z)\nBB = (Z, A)\nZ = (Z, Z)").unwrap();
+
+        assert_eq!(network.nodes_start(), vec![network.id("A").unwrap()]);
+        assert!(network.is_end_node(network.id("Z").unwrap()));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse_input("").unwrap_err(), vec![ParseError::MissingInstructions]);
+
+        assert_eq!(parse_input("LXR\n\nAAA = (AAA, AAA)").unwrap_err(),
+                   vec![ParseError::InvalidInstruction { line: 1, instruction: 'X' }]);
+
+        assert_eq!(parse_input("L\n\nAAA = (BBB, CCC)\nBBB = BBB, BBB\nCCC = (CCC CCC)").unwrap_err(), vec![
+            ParseError::MalformedNode { line: 4, content: "BBB = BBB, BBB".to_string() },
+            ParseError::MalformedNode { line: 5, content: "CCC = (CCC CCC)".to_string() },
+            ParseError::UnknownLabel { line: 3, label: "BBB".to_string() },
+            ParseError::UnknownLabel { line: 3, label: "CCC".to_string() },
+        ]);
+
+        assert_eq!(parse_input("L\n\nAAA = (AAA, ZZZ)\nAAA = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap_err(), vec![
+            ParseError::DuplicateDefinition { line: 4, label: "AAA".to_string(), first_line: 3 },
+        ]);
+    }
+
+    #[test]
+    fn steps() {
+        let (instructions, network) = parse_input(EXAMPLE).unwrap();
+        let aaa = network.id("AAA").unwrap();
+        let zzz = network.id("ZZZ").unwrap();
+
+        assert_eq!(network.steps(aaa, zzz, &instructions), Ok(2));
+        assert_eq!(network.steps(aaa, aaa, &instructions), Ok(0));
+    }
+
+    #[test]
+    fn steps_unreachable() {
+        let unreachable = |loop_after| Unreachable { start: "AAA".to_string(), target: "ZZZ".to_string(), loop_after };
+
+        // AAA -> BBB -> AAA -> BBB ...
+        let (instructions, network) = parse_input("L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        let aaa = network.id("AAA").unwrap();
+        let zzz = network.id("ZZZ").unwrap();
+
+        assert_eq!(network.steps(aaa, zzz, &instructions), Err(unreachable(2)));
+
+        // ZZZ would be right there, but the instructions never lead to it:
+        // AAA -L-> BBB -R-> AAA -L-> BBB ...
+        let (instructions, network) = parse_input("LR\n\nAAA = (BBB, ZZZ)\nBBB = (ZZZ, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert_eq!(network.steps(aaa, zzz, &instructions), Err(unreachable(2)));
+
+        // there is no way from AAA to ZZZ at all:
+        // AAA -> BBB -> CCC -> BBB ...
+        let (instructions, network) = parse_input("LR\n\nAAA = (BBB, BBB)\nBBB = (CCC, CCC)\nCCC = (BBB, BBB)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        let zzz = network.id("ZZZ").unwrap();
+        assert_eq!(network.steps(aaa, zzz, &instructions), Err(unreachable(3)));
+
+        assert_eq!(unreachable(3).to_string(), "ZZZ can not be reached from AAA, the walk loops after 3 steps");
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use crate::{parse_input, Direction};
+    use crate::cycle::solve;
+
+    #[derive(Debug)]
+    struct Generated {
+        instructions: Vec<Direction>,
+        labels: Vec<String>,
+        // edges[i] = (left, right), indices into labels
+        edges: Vec<(usize, usize)>,
+    }
+
+    impl Generated {
+        fn text(&self) -> String {
+            let instructions: String = self.instructions.iter()
+                .map(|direction| match direction { Direction::Left => 'L', Direction::Right => 'R' })
+                .collect();
+            let nodes: Vec<String> = self.labels.iter().zip(&self.edges)
+                .map(|(label, &(left, right))| format!("{label} = ({}, {})", self.labels[left], self.labels[right]))
+                .collect();
+            format!("{instructions}\n\n{}\n", nodes.join("\n"))
+        }
+    }
+
+    prop_compose! {
+        fn network()(size in 1..12usize)
+                    (instructions in prop::collection::vec(prop_oneof![Just(Direction::Left), Just(Direction::Right)], 1..6),
+                     kinds in prop::collection::vec(prop_oneof!["A", "Z", "X"], size),
+                     edges in prop::collection::vec((0..size, 0..size), size))
+                    -> Generated {
+            // labels like N3A, N4X, N5Z: starts end with A, ends with Z
+            let labels = kinds.iter().enumerate().map(|(i, kind)| format!("N{i}{kind}")).collect();
+            Generated { instructions, labels, edges }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn parse_round_trip(generated in network()) {
+            let (instructions, network) = parse_input(&generated.text()).unwrap();
+
+            prop_assert_eq!(&instructions, &generated.instructions);
+            prop_assert_eq!(network.len(), generated.labels.len());
+            for (label, &(left, right)) in generated.labels.iter().zip(&generated.edges) {
+                let id = network.id(label).unwrap();
+                prop_assert_eq!(network.label(network.next(id, Direction::Left)), &generated.labels[left]);
+                prop_assert_eq!(network.label(network.next(id, Direction::Right)), &generated.labels[right]);
+                prop_assert_eq!(network.is_end_node(id), label.ends_with('Z'));
+            }
+
+            let instructions: String = instructions.iter().map(Direction::to_string).collect();
+            prop_assert_eq!(format!("{instructions}\n\n{network}\n"), generated.text());
+        }
+
+        #[test]
+        fn steps_agree_with_walk(generated in network(), target in any::<prop::sample::Index>()) {
+            let (instructions, network) = parse_input(&generated.text()).unwrap();
+            let start = 0;
+            let target = target.index(network.len()) as u32;
+
+            match network.steps(start, target, &instructions) {
+                Ok(steps) => {
+                    let first = network.walk(start, &instructions).position(|id| id == target);
+                    prop_assert_eq!(first, Some(steps as usize));
+                }
+                Err(unreachable) => {
+                    // every state has been seen by then, the target is never going to show up
+                    let states = network.len() * instructions.len();
+                    prop_assert!(unreachable.loop_after as usize <= states);
+                    prop_assert!(network.walk(start, &instructions).take(2 * states + 1).all(|id| id != target));
+                }
+            }
+        }
+
+        #[test]
+        fn cycle_hits_agree_with_walk(generated in network()) {
+            let (instructions, network) = parse_input(&generated.text()).unwrap();
+
+            for start in network.ids() {
+                let cycle = network.cycle(start, &instructions);
+                for (step, id) in network.walk(start, &instructions).take(200).enumerate() {
+                    prop_assert_eq!(cycle.hits(step as u64), network.is_end_node(id), "start {}, step {}", start, step);
+                }
+            }
+        }
+
+        #[test]
+        fn solve_agrees_with_stepping_simultaneously(generated in network()) {
+            let (instructions, network) = parse_input(&generated.text()).unwrap();
+            let starts = network.nodes_start();
+            let cycles: Vec<_> = starts.iter().map(|&start| network.cycle(start, &instructions)).collect();
+
+            // the slow way of part 2, for as long as it takes to be sure for these small networks
+            let mut walks: Vec<_> = starts.iter().map(|&start| network.walk(start, &instructions)).collect();
+            let stepping = (0..5000u64).find(|_| {
+                let ids: Vec<u32> = walks.iter_mut().map(|walk| walk.next().unwrap()).collect();
+                ids.iter().all(|&id| network.is_end_node(id))
+            });
+
+            match solve(&cycles).unwrap() {
+                Some(step) if step < 5000 => prop_assert_eq!(stepping, Some(step)),
+                _ => prop_assert!(starts.is_empty() || stepping.is_none()),
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use std::env::args;
+use std::fs::{read_to_string, write};
+
+use aoc08::generator::generate;
+use aoc08::parse_input;
+use seeded::generate_command;
+
+fn main() {
+    // Usage: aoc08 <input> [<network.dot>]
+    //        aoc08 generate <instructions> <cycle,cycle,...> [<seed>] > <input>    (the answers go to stderr)
+    let usage = "usage: aoc08 <input> [<network.dot>] | aoc08 generate <instructions> <cycle,cycle,...> [<seed>]";
+    let args: Vec<String> = args().collect();
+
+    if generate_command(&args[1..], usage, 2, |sizes, seed, writer| {
+        let instructions = sizes[0].parse().expect("instructions must be a number");
+        let cycles: Vec<u64> = sizes[1].split(',')
+            .map(|cycle| cycle.parse().expect("cycles must be numbers"))
+            .collect();
+        generate(writer, seed, instructions, &cycles)
+    }) {
+        return;
+    }
+
+    let input = args.get(1).expect(usage);
+
+    let (instructions, network) = match parse_input(&read_to_string(input).unwrap()) {
+        Ok(parsed) => parsed,
+        Err(errors) => {
+            for error in errors { eprintln!("{}", error); }
+            std::process::exit(1);
+        }
+    };
+
+    for start in network.nodes_start() {
+        let ends: Vec<&str> = network.reachable_ends(start, &instructions).into_iter()
+            .map(|end| network.label(end))
+            .collect();
+        println!("{} reaches {:?}", network.label(start), ends);
+    }
+
+    let unreachable: Vec<&str> = network.unreachable_nodes(&instructions).into_iter()
+        .map(|id| network.label(id))
+        .collect();
+    println!("never reached: {:?}", unreachable);
+
+    let dead_ends: Vec<&str> = network.dead_ends(&instructions).into_iter()
+        .map(|id| network.label(id))
+        .collect();
+    println!("dead ends: {:?}", dead_ends);
+
+    if let Some(dot) = args.get(2) {
+        write(dot, network.to_dot(&instructions)).unwrap();
+    }
+}
@@ -1,113 +1,98 @@
-use std::collections::HashMap;
 use std::fs::read_to_string;
 
+use aoc08::cycle::{solve, Cycle};
+use aoc08::parse_input;
+use report::{fail, input_hash, options_or_exit, timed, Report};
 
 fn main() {
-    let (instructions, network)
-        = parse_input(read_to_string("src/example3").unwrap());
-
-    let mut instruction_cycle = instructions.chars().cycle();
-
-    let mut step = 0;
-    let mut current: Vec<Node> = network.nodes_start.clone();
-
-    println!("{} {:?}", step, current);
-
-    while current != network.nodes_end {
-        step += 1;
-        let instruction = instruction_cycle.next().unwrap();
-
-        let mut next = Vec::new();
-        for node in current {
-            let x = network.get_node_by_label(node.get_label_by_direction(instruction));
-            next.push(x);
-        }
-
-        current = next;
-        println!("{} {} {:?}", step, instruction, current);
+    // Usage: aoc08b [--format text|json] [[--input] <input>]
+    let options = options_or_exit();
+    let input = read_to_string(options.input_or_first("src/example3")).unwrap();
+
+    let (parsed, parse_time) = timed(|| parse_input(&input));
+    let (instructions, network) = parsed.unwrap_or_else(|errors| {
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        fail(8, 'b', options.format, errors.join("\n"))
+    });
+
+    let (steps, solve_time) = timed(|| {
+        let cycles: Vec<Cycle> = network.nodes_start().into_iter()
+            .map(|start| network.cycle(start, &instructions))
+            .collect();
+        solve(&cycles)
+    });
+
+    match steps {
+        Ok(Some(steps)) => Report { day: 8, part: 'b', answer: steps.to_string(), parse_time, solve_time, input_hash: input_hash(input.as_bytes()) }
+            .print(options.format),
+        Ok(None) => fail(8, 'b', options.format, "ghosts never meet on end nodes"),
+        Err(overflow) => fail(8, 'b', options.format, overflow),
     }
-
-    println!("{}", step);
 }
 
-fn parse_input(s: String) -> (String, Network) {
-    let (input_instructions, input_nodes)
-        = s.split_once("\n").unwrap();
 
-    let instructions = input_instructions.to_string();
-    let network = Network::from(input_nodes.trim());
+#[cfg(test)]
+mod ghosts {
+    use std::fs::read_to_string;
+    use aoc08::cycle::{solve, Cycle};
+    use aoc08::parse_input;
 
-    return (instructions, network);
-}
-
-#[derive(Debug)]
-struct Network {
-    nodes: HashMap<String, Node>,
-    nodes_start: Vec<Node>,
-    nodes_end: Vec<Node>,
-}
-
-impl Network {
-    fn new() -> Self {
-        Self {
-            nodes: HashMap::new(),
-            nodes_start: Vec::new(),
-            nodes_end: Vec::new(),
-        }
+    fn steps(input: &str) -> Option<u64> {
+        let (instructions, network) = parse_input(input).unwrap();
+        let cycles: Vec<Cycle> = network.nodes_start().into_iter()
+            .map(|start| network.cycle(start, &instructions))
+            .collect();
+        solve(&cycles).unwrap()
     }
 
-    fn from(s: &str) -> Self {
-        let mut network = Self::new();
-
-        for node in s.split("\n") {
-            network.insert(Node::from(node))
-        }
-
-        network
+    #[test]
+    fn examples() {
+        assert_eq!(steps(&read_to_string("src/example").unwrap()), Some(2));
+        assert_eq!(steps(&read_to_string("src/example2").unwrap()), Some(6));
+        assert_eq!(steps(&read_to_string("src/example3").unwrap()), Some(6));
     }
 
-    fn insert(&mut self, node: Node) {
-        self.nodes.insert(node.name.to_string(), node.clone());
-
-        if node.is_start_node() { self.nodes_start.push(node.clone()); }
-        if node.is_end_node() { self.nodes_end.push(node.clone()); }
-    }
-
-    fn get_node_by_label(&self, label: String) -> Node {
-        self.nodes.get(&label).unwrap().to_owned()
+    #[test]
+    fn offset_hits() {
+        // 11A -> 11B -> 11Z -> 11B -> 11Z ...: hits at 2, 4, 6, ...
+        // 22A -> 22Z -> 22B -> 22C -> 22Z ...: hits at 1, 4, 7, ...
+        // The least common multiple of the cycle lengths (6) is NOT a solution
+        let input = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)";
+        assert_eq!(steps(input), Some(4));
     }
-}
-
-#[derive(Debug, Clone)]
-#[derive(PartialEq)]
-struct Node {
-    name: String,
-    left: String,
-    right: String,
-}
 
-impl Node {
-    fn from(node_str: &str) -> Self {
-        Self {
-            name: node_str[0..=2].to_string(),
-            left: node_str[7..=9].to_string(),
-            right: node_str[12..=14].to_string(),
-        }
+    #[test]
+    fn prefix_hits() {
+        // 11A -> 11Z -> 11B -> 11B ...: the only hit at 1 happens before the cycle
+        // 22A -> 22Z -> 22Z ...: hits at every step from 1 on
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22Z, 22Z)";
+        assert_eq!(steps(input), Some(1));
     }
 
-    fn get_label_by_direction(&self, direction: char) -> String {
-        match direction {
-            'L' => self.left.clone(),
-            'R' => self.right.clone(),
-            _ => panic!()
-        }
+    #[test]
+    fn never() {
+        // 11A -> 11Z -> 11Z ...: hits at every step from 1 on
+        // 22A -> 22B -> 22B ...: never reaches an end node
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22B, 22B)";
+        assert_eq!(steps(input), None);
     }
-
-    fn is_start_node(&self) -> bool { self.name.ends_with("A") }
-
-    fn is_end_node(&self) -> bool { self.name.ends_with("Z") }
 }
-
-
-
-
@@ -0,0 +1,25 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc10a::{steps_to_farthest, Grid};
+use aoc10a::generator::generate;
+
+fn phases(c: &mut Criterion) {
+    let mut maze = Vec::new();
+    generate(&mut maze, 0x2023_1210, 140, 140).unwrap();
+    let maze = String::from_utf8(maze).unwrap();
+
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("aoc10a 140x140 tiles", |b| b.iter(|| Grid::from(black_box(&maze).clone())));
+    group.finish();
+
+    let grid = Grid::from(maze);
+
+    let mut group = c.benchmark_group("solve");
+    group.bench_function("aoc10a 140x140 tiles", |b| b.iter(|| steps_to_farthest(black_box(&grid))));
+    group.finish();
+}
+
+criterion_group!(benches, phases);
+criterion_main!(benches);
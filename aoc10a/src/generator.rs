@@ -0,0 +1,143 @@
+use std::io::{self, Write};
+
+static JUNK: &[u8] = b"|-LJ7F....";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    // steps to the point of the loop farthest from the start
+    pub part1: u64,
+}
+
+impl seeded::Answers for Answers {
+    fn parts(&self) -> Vec<u128> { vec![self.part1.into()] }
+}
+
+pub fn generate<W: Write>(mut writer: W, seed: u64, rows: usize, columns: usize) -> io::Result<Answers> {
+    // A maze like the puzzle input: one loop through the start, surrounded by junk pipes.
+    // The loop is the boundary of a shape of columns of fields, one between every two columns of tiles.
+    // Top and bottom of the fields jump up and down from column to column,
+    // always overlapping the previous column, so the loop never touches itself,
+    // but winds through most of the maze.
+    //
+    // The answer is half the length of the loop, as it is laid out.
+    assert!(rows >= 2 && columns >= 2);
+
+    let mut random = seeded::random(seed);
+
+    let mut spans: Vec<(usize, usize)> = Vec::with_capacity(columns - 1);
+    while spans.len() < columns - 1 {
+        let mut from = random(rows - 1);
+        let mut to = from + 1 + random(rows - 1 - from);
+        if let Some(&(previous_from, previous_to)) = spans.last() {
+            if from >= previous_to { from = previous_to - 1; }
+            if to <= previous_from { to = previous_from + 1; }
+        }
+        spans.push((from, to));
+    }
+
+    let main_loop = boundary(&spans);
+    let mut fields: Vec<Vec<char>> = (0..rows)
+        .map(|_| (0..columns).map(|_| JUNK[random(JUNK.len())] as char).collect())
+        .collect();
+    let start = random(main_loop.len());
+    draw(&mut fields, &main_loop, start);
+
+    for row in &fields {
+        writeln!(writer, "{}", row.iter().collect::<String>())?;
+    }
+
+    writer.flush()?;
+    Ok(Answers { part1: main_loop.len() as u64 / 2 })
+}
+
+pub fn boundary(spans: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    // The tiles of the loop around the columns of fields, in order.
+    // Column c of fields spans from row from to row to, between the tile columns c and c + 1.
+    let mut main_loop = vec![(spans[0].0, 0)];
+    // along the top, down the right, back along the bottom and up the left
+    for (c, &(from, _)) in spans.iter().enumerate() {
+        go(&mut main_loop, (from, c));
+        go(&mut main_loop, (from, c + 1));
+    }
+    for (c, &(_, to)) in spans.iter().enumerate().rev() {
+        go(&mut main_loop, (to, c + 1));
+        go(&mut main_loop, (to, c));
+    }
+    go(&mut main_loop, (spans[0].0, 0));
+    main_loop.pop();
+
+    main_loop
+}
+
+fn go(main_loop: &mut Vec<(usize, usize)>, (r, c): (usize, usize)) {
+    // extends the loop tile by tile, first sideways, then up or down
+    let &(mut cr, mut cc) = main_loop.last().unwrap();
+    while (cr, cc) != (r, c) {
+        if cc < c { cc += 1 } else if cc > c { cc -= 1 } else if cr < r { cr += 1 } else { cr -= 1 }
+        main_loop.push((cr, cc));
+    }
+}
+
+pub fn draw(rows: &mut [Vec<char>], main_loop: &[(usize, usize)], start: usize) {
+    // Lays the loop over the junk, and marks its tile number start with S.
+    let n = main_loop.len();
+    for (i, &(r, c)) in main_loop.iter().enumerate() {
+        rows[r][c] = pipe((r, c), main_loop[(i + n - 1) % n], main_loop[(i + 1) % n]);
+    }
+
+    let (sr, sc) = main_loop[start];
+    rows[sr][sc] = 'S';
+
+    // pipes next to the start must not lead into it, unless they are part of the loop
+    let leads_into_start = [(-1, 0, "|7F"), (1, 0, "|LJ"), (0, -1, "-LF"), (0, 1, "-J7")];
+    for (dr, dc, pipes) in leads_into_start {
+        let (r, c) = (sr as isize + dr, sc as isize + dc);
+        if r < 0 || c < 0 || r as usize >= rows.len() || c as usize >= rows[0].len() { continue; }
+        let (r, c) = (r as usize, c as usize);
+        if !main_loop.contains(&(r, c)) && pipes.contains(rows[r][c]) { rows[r][c] = '.'; }
+    }
+}
+
+fn pipe((r, c): (usize, usize), a: (usize, usize), b: (usize, usize)) -> char {
+    // the pipe on (r, c) connecting its neighbours a and b
+    let direction = |(nr, nc): (usize, usize)| match (nr as isize - r as isize, nc as isize - c as isize) {
+        (-1, 0) => 'N',
+        (1, 0) => 'S',
+        (0, 1) => 'E',
+        _ => 'W',
+    };
+    let mut directions = [direction(a), direction(b)];
+    directions.sort_unstable();
+
+    match directions {
+        ['N', 'S'] => '|',
+        ['E', 'W'] => '-',
+        ['E', 'N'] => 'L',
+        ['N', 'W'] => 'J',
+        ['S', 'W'] => '7',
+        ['E', 'S'] => 'F',
+        _ => unreachable!(),
+    }
+}
+
+
+#[cfg(test)]
+mod synthetic {
+    use crate::generator::generate;
+    use crate::{steps_to_farthest, Grid};
+
+    #[test]
+    fn generate_answers() {
+        let mut maze = Vec::new();
+        let answers = generate(&mut maze, 42, 140, 140).unwrap();
+
+        let maze = String::from_utf8(maze).unwrap();
+        assert_eq!(maze.matches('S').count(), 1);
+
+        let grid: Grid = maze.into();
+        assert_eq!((grid.height(), grid.width()), (140, 140));
+        assert_eq!(steps_to_farthest(&grid) as u64, answers.part1);
+        // the loop winds through the maze, not just around its edge
+        assert!(2 * answers.part1 > 140 * 140 / 3);
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::Direction::{East, North, South, West};
+
+pub mod generator;
+
+pub fn steps_to_farthest(grid: &Grid) -> usize {
+    // The loop is followed both ways from the start at once,
+    // the point farthest from the start is where the ways meet.
+    let mut way_left = HashMap::new();
+    let mut way_right = HashMap::new();
+
+    // both ways start with the start
+    let mut step = 0;
+    way_left.insert(step, grid.start.clone());
+    way_right.insert(step, grid.start.clone());
+
+    // find neighbors of the start
+    step += 1;
+
+    let x = grid.get_neighbors(&grid.start);
+
+    let mut left_next = x[0].clone();
+    let mut right_next = x[1].clone();
+
+    way_left.insert(1, left_next.clone());
+    way_right.insert(1, right_next.clone());
+
+    // follow the rest of the ways,
+    // until the ways meet at the end
+    while left_next != right_next {
+        step += 1;
+
+        // follow left way
+        let left_current_neighbors = grid.get_neighbors(&way_left[&(step - 1)]);
+        for neighbor in left_current_neighbors {
+            if neighbor != way_left[&(step - 2)] && neighbor.char != 'S' {
+                left_next = neighbor;
+            }
+        }
+        way_left.insert(step, left_next.clone());
+
+        // follow right way
+        let right_current_neighbors = grid.get_neighbors(&way_right[&(step - 1)]);
+        for neighbor in right_current_neighbors {
+            if neighbor != way_right[&(step - 2)] && neighbor.char != 'S' {
+                right_next = neighbor;
+            }
+        }
+        way_right.insert(step, right_next.clone());
+    }
+
+    assert_eq!(way_left.len(), way_right.len());
+    step
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Grid {
+    tiles: HashMap<usize, HashMap<usize, Tile>>,
+    start: Tile,
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // the sketch as read, the start still marked with S
+        for x in 0..self.height() {
+            if x > 0 { writeln!(f)?; }
+            for y in 0..self.tiles[&x].len() {
+                write!(f, "{}", self.get(x, y).char)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<String> for Grid {
+    fn from(s: String) -> Self {
+        let mut grid = Grid::new();
+
+        /* example2:
+        -L|F7
+        7S-7|
+        L|7||
+        -L-J|
+        L|-JF
+         */
+        for (x, input_row) in s.trim().split("\n").enumerate() {
+            let mut row = HashMap::new();
+            // "-L|F7"
+            for (y, char) in input_row.chars().enumerate() {
+                let connects = Grid::connections_by_char(char);
+                let tile = Tile { x, y, char, connects };
+                row.insert(y, tile.clone());
+
+                if char == 'S' { grid.start = tile.clone() }
+            }
+
+            grid.tiles.insert(x, row);
+        }
+
+        grid.start.connects.append(&mut grid.connections_by_position(
+            grid.start.x,
+            grid.start.y,
+        ));
+
+        grid
+    }
+}
+
+impl Grid {
+    fn new() -> Self {
+        let tiles = HashMap::new();
+        let start = Tile::new();
+        Self { tiles, start }
+    }
+
+
+    fn connections_by_char(char: char) -> Vec<Direction> {
+        /*
+        | is a vertical pipe connecting north and south.
+        - is a horizontal pipe connecting east and west.
+        L is a 90-degree bend connecting north and east.
+        J is a 90-degree bend connecting north and west.
+        7 is a 90-degree bend connecting south and west.
+        F is a 90-degree bend connecting south and east.
+        . is ground; there is no pipe in this tile.
+        S is the starting position of the animal; there is a pipe on this tile, but your sketch doesn't show what shape the pipe has.
+         */
+
+        match char {
+            '|' => vec![North, South],
+            '-' => vec![East, West],
+            'L' => vec![North, East],
+            'J' => vec![North, West],
+            '7' => vec![South, West],
+            'F' => vec![South, East],
+            '.' => vec![],
+            'S' => vec![], // Empty for now, needs to be filled later!
+            _ => panic!()
+        }
+    }
+
+    fn connections_by_position(&self, x: usize, y: usize) -> Vec<Direction> {
+        // for all directions that are next to this position, check if there is a tile.
+        // iff there is a tile, check if that tile connects to this.
+        // iff it does, this position also connects to that position.
+
+        let mut connections = Vec::new();
+
+        if x > 0 && self.get(x - 1, y).connects_to(South) { connections.push(North) }
+        if y > 0 && self.get(x, y - 1).connects_to(East) { connections.push(West) }
+        if x + 1 < self.height() && self.get(x + 1, y).connects_to(North) { connections.push(South) }
+        if y + 1 < self.width() && self.get(x, y + 1).connects_to(West) { connections.push(East) }
+
+        connections
+    }
+
+    fn height(&self) -> usize {
+        self.tiles.len()
+    }
+
+    fn width(&self) -> usize {
+        assert!(self.height() > 0);
+        self.tiles.get(&0).unwrap().len()
+    }
+
+    fn get(&self, x: usize, y: usize) -> Tile {
+        self.tiles.get(&x).unwrap().get(&y).unwrap().to_owned()
+    }
+
+    fn get_neighbors(&self, tile: &Tile) -> Vec<Tile> {
+        let mut neighbors = Vec::new();
+
+        let (x, y) = (tile.x, tile.y);
+
+        for connects in &tile.connects {
+            match connects {
+                North => neighbors.push(self.get(x - 1, y)),
+                South => neighbors.push(self.get(x + 1, y)),
+                East => neighbors.push(self.get(x, y + 1)),
+                West => neighbors.push(self.get(x, y - 1)),
+            }
+        }
+
+        assert_eq!(neighbors.len(), 2);
+        neighbors
+    }
+}
+
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+struct Tile {
+    x: usize,
+    y: usize,
+    char: char,
+    connects: Vec<Direction>,
+}
+
+impl Tile {
+    fn new() -> Self {
+        // this might crash when a Tile really is on x:0,y:0
+        Self { x: 0, y: 0, char: '.', connects: vec![] }
+    }
+
+    fn connects_to(&self, direction: Direction) -> bool {
+        self.connects.contains(&direction)
+    }
+}
+
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum Direction { North, South, East, West }
+
+
+#[cfg(test)]
+mod farthest {
+    use std::fs::read_to_string;
+
+    use crate::{steps_to_farthest, Grid};
+
+    #[test]
+    fn examples() {
+        assert_eq!(steps_to_farthest(&read_to_string("src/example").unwrap().into()), 4);
+        assert_eq!(steps_to_farthest(&read_to_string("src/example2").unwrap().into()), 8);
+    }
+
+    #[test]
+    fn start_in_the_last_row_and_column() {
+        // there is nothing south or east of the start to connect to
+        let grid: Grid = ".F-7\n.|.|\n.L-S\n".to_string().into();
+        assert_eq!(steps_to_farthest(&grid), 4);
+    }
+}
+
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use crate::generator::{boundary, draw};
+    use crate::{steps_to_farthest, Grid};
+
+    #[derive(Debug)]
+    struct Maze {
+        rows: Vec<Vec<char>>,
+        // the tiles of the loop, in order
+        main_loop: Vec<(usize, usize)>,
+        start: usize,
+    }
+
+    fn maze() -> impl Strategy<Value=Maze> {
+        // The loop goes around a shape of columns of fields, each column overlapping the next one,
+        // which makes the shape free of holes and its boundary a loop that never touches itself.
+        // The tiles of the loop are the corners of the fields.
+        let columns = prop::collection::vec((0..6usize, 1..5usize), 1..8);
+        let margins = (0..3usize, 0..3usize, 0..3usize, 0..3usize);
+        let junk = prop::collection::vec(prop::sample::select(vec!['|', '-', 'L', 'J', '7', 'F', '.']), 400);
+
+        (columns, margins, junk, any::<prop::sample::Index>())
+            .prop_map(|(columns, (top, left, bottom, right), junk, start)| {
+                let mut spans: Vec<(usize, usize)> = Vec::new();
+                for (from, length) in columns {
+                    let (mut from, mut to) = (from, from + length);
+                    if let Some(&(previous_from, previous_to)) = spans.last() {
+                        if from >= previous_to { from = previous_to - 1; }
+                        if to <= previous_from { to = previous_from + 1; }
+                    }
+                    spans.push((from, to));
+                }
+
+                let main_loop: Vec<(usize, usize)> = boundary(&spans).into_iter().map(|(r, c)| (r + top, c + left)).collect();
+                let height = main_loop.iter().map(|&(r, _)| r).max().unwrap() + 1 + bottom;
+                let width = main_loop.iter().map(|&(_, c)| c).max().unwrap() + 1 + right;
+
+                let mut rows: Vec<Vec<char>> = (0..height)
+                    .map(|r| (0..width).map(|c| junk[(r * width + c) % junk.len()]).collect())
+                    .collect();
+                let start = start.index(main_loop.len());
+                draw(&mut rows, &main_loop, start);
+
+                Maze { rows, main_loop, start }
+            })
+    }
+
+    fn text(rows: &[Vec<char>]) -> String {
+        rows.iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+    }
+
+    #[test]
+    fn print_example() {
+        for example in ["src/example", "src/example2"] {
+            let example = std::fs::read_to_string(example).unwrap();
+            let grid: Grid = example.clone().into();
+
+            assert_eq!(grid.to_string(), example.trim_end());
+            assert_eq!(Grid::from(grid.to_string()), grid);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn parse_round_trip(maze in maze()) {
+            let text = text(&maze.rows);
+            let grid: Grid = text.clone().into();
+            prop_assert_eq!(grid.to_string(), text.trim_end());
+
+            prop_assert_eq!((grid.height(), grid.width()), (maze.rows.len(), maze.rows[0].len()));
+            for (r, row) in maze.rows.iter().enumerate() {
+                for (c, &char) in row.iter().enumerate() {
+                    let tile = grid.get(r, c);
+                    prop_assert_eq!((tile.x, tile.y, tile.char), (r, c, char));
+                }
+            }
+
+            // the start connects to the loop, both ways
+            prop_assert_eq!((grid.start.x, grid.start.y), maze.main_loop[maze.start]);
+            prop_assert_eq!(grid.start.connects.len(), 2);
+            let n = maze.main_loop.len();
+            let mut neighbors: Vec<(usize, usize)> = grid.get_neighbors(&grid.start).iter().map(|tile| (tile.x, tile.y)).collect();
+            let mut expect = vec![maze.main_loop[(maze.start + 1) % n], maze.main_loop[(maze.start + n - 1) % n]];
+            neighbors.sort_unstable();
+            expect.sort_unstable();
+            prop_assert_eq!(neighbors, expect);
+        }
+
+        #[test]
+        fn farthest_is_half_way_around(maze in maze()) {
+            let grid: Grid = text(&maze.rows).into();
+
+            prop_assert_eq!(steps_to_farthest(&grid), maze.main_loop.len() / 2);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    // seconds since the epoch
+    pub time: u64,
+    pub commit: String,
+    // mean time in nanoseconds, by benchmark, e.g. "solve/aoc05b 1000 ranges per map, brute force"
+    pub results: BTreeMap<String, f64>,
+}
+
+impl Run {
+    fn to_json(&self) -> Value {
+        json!({ "time": self.time, "commit": self.commit, "results": self.results })
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let results = value["results"].as_object()?.iter()
+            .map(|(id, nanos)| Some((id.clone(), nanos.as_f64()?)))
+            .collect::<Option<_>>()?;
+
+        Some(Self {
+            time: value["time"].as_u64()?,
+            commit: value["commit"].as_str()?.to_string(),
+            results,
+        })
+    }
+}
+
+pub fn parse_history(s: &str) -> Result<Vec<Run>, String> {
+    // [{"time": 1700000000, "commit": "7fe77d4", "results": {"parse/aoc04 100000 cards": 31250000.0, ...}}, ...]
+    let value: Value = serde_json::from_str(s).map_err(|error| error.to_string())?;
+
+    value.as_array().ok_or("expected a list of runs")?.iter()
+        .enumerate()
+        .map(|(i, run)| Run::from_json(run).ok_or(format!("run {} is not a run of benchmarks", i + 1)))
+        .collect()
+}
+
+pub fn print_history(history: &[Run]) -> String {
+    let runs: Vec<Value> = history.iter().map(Run::to_json).collect();
+    serde_json::to_string_pretty(&runs).unwrap()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    pub id: String,
+    pub nanos: f64,
+    // the latest earlier run of the same benchmark, if any
+    pub previous: Option<f64>,
+    pub regression: bool,
+}
+
+pub fn compare(history: &[Run], current: &Run, threshold: f64) -> Vec<Change> {
+    // Every benchmark is compared to its latest earlier run,
+    // as not every run benchmarks every day.
+    // Slower by more than the threshold (0.1 for 10 %) counts as a regression.
+    current.results.iter()
+        .map(|(id, &nanos)| {
+            let previous = history.iter().rev().find_map(|run| run.results.get(id).copied());
+            let regression = previous.is_some_and(|previous| nanos > previous * (1.0 + threshold));
+            Change { id: id.clone(), nanos, previous, regression }
+        })
+        .collect()
+}
+
+pub fn format_nanos(nanos: f64) -> String {
+    match nanos {
+        n if n >= 1e9 => format!("{:.3} s", n / 1e9),
+        n if n >= 1e6 => format!("{:.3} ms", n / 1e6),
+        n if n >= 1e3 => format!("{:.3} µs", n / 1e3),
+        n => format!("{:.3} ns", n),
+    }
+}
+
+
+#[cfg(test)]
+mod regressions {
+    use std::collections::BTreeMap;
+
+    use crate::history::{compare, format_nanos, parse_history, print_history, Change, Run};
+
+    fn run(time: u64, results: &[(&str, f64)]) -> Run {
+        let results: BTreeMap<String, f64> = results.iter().map(|&(id, nanos)| (id.to_string(), nanos)).collect();
+        Run { time, commit: format!("c{time}"), results }
+    }
+
+    #[test]
+    fn compare_to_latest_earlier_run() {
+        let history = vec![
+            run(1, &[("parse/aoc04", 100.0), ("solve/aoc04", 100.0)]),
+            run(2, &[("parse/aoc04", 200.0)]),
+        ];
+        let current = run(3, &[("parse/aoc04", 215.0), ("solve/aoc04", 111.0), ("solve/aoc05b", 1.0)]);
+
+        assert_eq!(compare(&history, &current, 0.1), vec![
+            Change { id: "parse/aoc04".to_string(), nanos: 215.0, previous: Some(200.0), regression: false },
+            Change { id: "solve/aoc04".to_string(), nanos: 111.0, previous: Some(100.0), regression: true },
+            Change { id: "solve/aoc05b".to_string(), nanos: 1.0, previous: None, regression: false },
+        ]);
+        assert!(compare(&[], &current, 0.1).iter().all(|change| !change.regression));
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let history = vec![run(1, &[("parse/aoc04 100000 cards", 31250000.5)]), run(2, &[])];
+        assert_eq!(parse_history(&print_history(&history)), Ok(history));
+
+        assert_eq!(parse_history("[]"), Ok(vec![]));
+        assert_eq!(parse_history("{}"), Err("expected a list of runs".to_string()));
+        assert_eq!(parse_history(r#"[{"time": 1}]"#), Err("run 1 is not a run of benchmarks".to_string()));
+        assert!(parse_history("[").is_err());
+    }
+
+    #[test]
+    fn units() {
+        assert_eq!(format_nanos(12.0), "12.000 ns");
+        assert_eq!(format_nanos(2214705.9), "2.215 ms");
+        assert_eq!(format_nanos(3.5e9), "3.500 s");
+    }
+}
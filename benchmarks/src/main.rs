@@ -0,0 +1,137 @@
+use std::env::{self, args};
+use std::fs::{read_dir, read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::process::{exit, Command, Stdio};
+use std::time::SystemTime;
+
+use serde_json::Value;
+
+use history::{compare, format_nanos, parse_history, print_history, Run};
+
+mod history;
+
+fn main() {
+    // Usage: benchmarks [--threshold <percent>] [--history <file>] [<day>...] [-- <criterion options>]
+    // Runs the parse and solve benchmarks (benches/phases.rs) of the days (all of them if none are given),
+    // adds the timings to the history (bench_history.json next to the days)
+    // and flags every benchmark slower than the threshold (10 % unless told otherwise) compared to its previous run.
+    let usage = "usage: benchmarks [--threshold <percent>] [--history <file>] [<day>...] [-- <criterion options>]";
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf();
+
+    let mut threshold = 10.0;
+    let mut history_path = root.join("bench_history.json");
+    let mut days: Vec<String> = Vec::new();
+    let mut criterion: Vec<String> = Vec::new();
+
+    let mut args = args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threshold" => threshold = args.next().expect(usage).parse().expect("threshold must be a number"),
+            "--history" => history_path = PathBuf::from(args.next().expect(usage)),
+            "--" => criterion.extend(args.by_ref()),
+            _ => days.push(arg),
+        }
+    }
+    if days.is_empty() { days = benchmarked_days(&root); }
+
+    let history = match read_to_string(&history_path) {
+        Ok(s) => parse_history(&s).unwrap_or_else(|error| {
+            eprintln!("{}: {}", history_path.display(), error);
+            exit(1);
+        }),
+        Err(_) => Vec::new(),
+    };
+
+    let started = SystemTime::now();
+    let time = started.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+    let mut current = Run { time, commit: commit(&root), results: Default::default() };
+
+    for day in &days {
+        let dir = root.join(day);
+        eprintln!("benchmarking {day}");
+
+        // criterion reports on stdout and stderr, only the summary below is of interest
+        let output = Command::new(env::var("CARGO").unwrap_or("cargo".to_string()))
+            .args(["bench", "--quiet", "--bench", "phases", "--manifest-path"])
+            .arg(dir.join("Cargo.toml"))
+            .args(["--", "--noplot"])
+            .args(&criterion)
+            .stdout(Stdio::null())
+            .output()
+            .unwrap();
+        if !output.status.success() {
+            eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+            eprintln!("{day}: benchmarks failed");
+            exit(1);
+        }
+
+        current.results.extend(estimates(&dir, started));
+    }
+
+    let changes = compare(&history, &current, threshold / 100.0);
+    let width = changes.iter().map(|change| change.id.len()).max().unwrap_or(0);
+    for change in &changes {
+        let difference = change.previous.map_or("new".to_string(), |previous| {
+            format!("{:+.1} %", (change.nanos / previous - 1.0) * 100.0)
+        });
+        let flag = if change.regression { "  REGRESSION" } else { "" };
+        println!("{:<width$}  {:>12}  {:>9}{flag}", change.id, format_nanos(change.nanos), difference);
+    }
+
+    let regressions = changes.iter().filter(|change| change.regression).count();
+    println!("{} benchmarks, {} regressions (slower by more than {} %)", changes.len(), regressions, threshold);
+
+    let mut history = history;
+    history.push(current);
+    write(&history_path, print_history(&history)).unwrap();
+
+    if regressions > 0 { exit(1); }
+}
+
+fn benchmarked_days(root: &Path) -> Vec<String> {
+    let mut days: Vec<String> = read_dir(root).unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("benches").join("phases.rs").is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    days.sort();
+    days
+}
+
+fn estimates(dir: &Path, since: SystemTime) -> Vec<(String, f64)> {
+    // Criterion keeps the latest estimates of every benchmark in target/criterion/<group>/<benchmark>/new.
+    // Benchmarks that were not run this time (renamed or filtered out) keep their old files, so those are left out.
+    let target = env::var_os("CARGO_TARGET_DIR").map_or_else(|| dir.join("target"), PathBuf::from);
+    let mut estimates = Vec::new();
+
+    for group in ["parse", "solve"] {
+        let Ok(benchmarks) = read_dir(target.join("criterion").join(group)) else { continue };
+
+        for benchmark in benchmarks.filter_map(|entry| entry.ok()) {
+            let new = benchmark.path().join("new");
+            let fresh = new.join("estimates.json").metadata()
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| modified >= since);
+            if !fresh { continue; }
+
+            let json = |file: &str| -> Option<Value> { serde_json::from_str(&read_to_string(new.join(file)).ok()?).ok() };
+            let (Some(benchmark), Some(estimate)) = (json("benchmark.json"), json("estimates.json")) else { continue };
+
+            if let (Some(id), Some(mean)) = (benchmark["full_id"].as_str(), estimate["mean"]["point_estimate"].as_f64()) {
+                estimates.push((id.to_string(), mean));
+            }
+        }
+    }
+
+    estimates
+}
+
+fn commit(root: &Path) -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(root)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map_or("unknown".to_string(), |output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,241 @@
+use std::env::args;
+use std::fmt;
+use std::io::{self, Read};
+use std::process::exit;
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    // the answer alone
+    Text,
+    // one line of JSON with the answer, timings and input hash
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub format: Format,
+    // --input <path>, for solvers whose first argument is something else
+    pub input: Option<String>,
+    // the rest, in order
+    pub args: Vec<String>,
+}
+
+impl Options {
+    pub fn input_or_first<'a>(&'a self, default: &'a str) -> &'a str {
+        // the input given by --input, or else as the first argument
+        self.input.as_deref().or(self.args.first().map(String::as_str)).unwrap_or(default)
+    }
+}
+
+pub fn options(args: impl IntoIterator<Item=String>) -> Result<Options, String> {
+    // Takes --format text|json and --input <path> (or --format=json, --input=path) out of the arguments,
+    // anywhere they are. The rest is left to the solver, in order.
+    let mut options = Options { format: Format::Text, input: None, args: Vec::new() };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (option, value) = match arg.split_once('=') {
+            Some((option, value)) => (option, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+
+        match option {
+            "--format" => {
+                let value = value.or_else(|| args.next()).ok_or("--format needs text or json")?;
+                options.format = match value.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    _ => return Err(format!("unknown format '{value}', expected text or json")),
+                };
+            }
+            "--input" => options.input = Some(value.or_else(|| args.next()).ok_or("--input needs a path")?),
+            _ => options.args.push(arg.clone()),
+        }
+    }
+
+    Ok(options)
+}
+
+pub fn options_or_exit() -> Options {
+    // The options of the program's arguments (without its name), leaving with the error if they are wrong
+    options(args().skip(1)).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    })
+}
+
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+pub fn input_hash(input: &[u8]) -> u64 {
+    // FNV-1a, which (unlike the hasher of the standard library) stays the same from release to release,
+    // so inputs can be told apart across runs and machines
+    fnv_1a(0xcbf2_9ce4_8422_2325, input)
+}
+
+fn fnv_1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+pub struct HashingReader<R> {
+    // Hashes what is read through it, for solvers streaming inputs too large to hold in memory
+    inner: R,
+    hash: u64,
+}
+
+impl<R: Read> HashingReader<R> {
+    pub fn new(inner: R) -> Self {
+        HashingReader { inner, hash: input_hash(b"") }
+    }
+
+    pub fn input_hash(&self) -> u64 {
+        // the input_hash of all bytes read so far
+        self.hash
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hash = fnv_1a(self.hash, &buf[..read]);
+        Ok(read)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub day: u8,
+    // a or b
+    pub part: char,
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    pub input_hash: u64,
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        // {"day":4,"part":"a","answer":"26346","parse_time_ns":512000,"solve_time_ns":3100,"input_hash":"af63dc4c8601ec8c"}
+        // The answer is a string, as answers (u128 in 4b) may not fit the numbers JSON readers know.
+        json!({
+            "day": self.day,
+            "part": self.part,
+            "answer": self.answer,
+            "parse_time_ns": self.parse_time.as_nanos() as u64,
+            "solve_time_ns": self.solve_time.as_nanos() as u64,
+            "input_hash": format!("{:016x}", self.input_hash),
+        }).to_string()
+    }
+
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Text => println!("{}", self.answer),
+            Format::Json => println!("{}", self.to_json()),
+        }
+    }
+}
+
+pub fn error_to_json(day: u8, part: char, error: &str) -> String {
+    // {"day":8,"part":"a","error":"ZZZ can not be reached from AAA, the walk loops after 6 steps"}
+    json!({ "day": day, "part": part, "error": error }).to_string()
+}
+
+pub fn fail(day: u8, part: char, format: Format, error: impl fmt::Display) -> ! {
+    // Solvers without an answer leave with 1, in JSON on stdout in place of the Report
+    match format {
+        Format::Text => eprintln!("{}", error),
+        Format::Json => println!("{}", error_to_json(day, part, &error.to_string())),
+    }
+    exit(1)
+}
+
+
+#[cfg(test)]
+mod formats {
+    use std::io::Read;
+    use std::time::Duration;
+
+    use crate::{error_to_json, input_hash, options, Format, HashingReader, Options, Report};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn format_options() {
+        let text = |rest: &[&str]| Ok(Options { format: Format::Text, input: None, args: args(rest) });
+        let json = |rest: &[&str]| Ok(Options { format: Format::Json, input: None, args: args(rest) });
+
+        assert_eq!(options(args(&[])), text(&[]));
+        assert_eq!(options(args(&["src/input"])), text(&["src/input"]));
+        assert_eq!(options(args(&["--format", "json", "src/input"])), json(&["src/input"]));
+        assert_eq!(options(args(&["src/input", "--format=json", "german"])), json(&["src/input", "german"]));
+        assert_eq!(options(args(&["--format", "json", "--format", "text"])), text(&[]));
+
+        assert_eq!(options(args(&["--format"])), Err("--format needs text or json".to_string()));
+        assert_eq!(options(args(&["--format", "xml"])), Err("unknown format 'xml', expected text or json".to_string()));
+    }
+
+    #[test]
+    fn input_option() {
+        let given = options(args(&["german", "--input", "src/example2", "--format=json"])).unwrap();
+        assert_eq!(given, Options { format: Format::Json, input: Some("src/example2".to_string()), args: args(&["german"]) });
+        assert_eq!(given.input_or_first("src/input"), "src/example2");
+
+        let given = options(args(&["--input=src/example"])).unwrap();
+        assert_eq!(given.input, Some("src/example".to_string()));
+
+        assert_eq!(options(args(&["src/example"])).unwrap().input_or_first("src/input"), "src/example");
+        assert_eq!(options(args(&[])).unwrap().input_or_first("src/input"), "src/input");
+        assert_eq!(options(args(&["--input"])), Err("--input needs a path".to_string()));
+    }
+
+    #[test]
+    fn fnv_1a() {
+        assert_eq!(input_hash(b""), 0xcbf29ce484222325);
+        assert_eq!(input_hash(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(input_hash(b"foobar"), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn hashing_reader() {
+        // read in pieces of 4 bytes, the hash is that of the whole input
+        let mut reader = HashingReader::new(&b"foobar"[..]);
+        let mut piece = [0; 4];
+        while reader.read(&mut piece).unwrap() > 0 {}
+        assert_eq!(reader.input_hash(), input_hash(b"foobar"));
+
+        assert_eq!(HashingReader::new(&b""[..]).input_hash(), input_hash(b""));
+    }
+
+    #[test]
+    fn json() {
+        let report = Report {
+            day: 4,
+            part: 'a',
+            answer: "26346".to_string(),
+            parse_time: Duration::from_micros(512),
+            solve_time: Duration::from_nanos(3100),
+            input_hash: 0xaf63dc4c8601ec8c,
+        };
+        assert_eq!(report.to_json(),
+                   r#"{"day":4,"part":"a","answer":"26346","parse_time_ns":512000,"solve_time_ns":3100,"input_hash":"af63dc4c8601ec8c"}"#);
+
+        let report = Report { answer: "say \"hi\"\n".to_string(), input_hash: 1, ..report };
+        assert_eq!(report.to_json(),
+                   r#"{"day":4,"part":"a","answer":"say \"hi\"\n","parse_time_ns":512000,"solve_time_ns":3100,"input_hash":"0000000000000001"}"#);
+    }
+
+    #[test]
+    fn json_errors() {
+        assert_eq!(error_to_json(8, 'a', "ZZZ can not be reached from AAA"),
+                   r#"{"day":8,"part":"a","error":"ZZZ can not be reached from AAA"}"#);
+        assert_eq!(error_to_json(8, 'a', "say \"hi\""), r#"{"day":8,"part":"a","error":"say \"hi\""}"#);
+    }
+}
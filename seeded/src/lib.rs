@@ -0,0 +1,88 @@
+use std::io::{self, stdout, BufWriter, StdoutLock};
+
+pub fn random_u64(seed: u64) -> impl FnMut(u64) -> u64 {
+    // A xorshift, so generated inputs stay the same for the same seed, from run to run and machine to machine.
+    // 0 would stay 0 forever, so it starts from 1 instead.
+    // Returns numbers below the bound it is given, which must not be 0, as there are none below 0.
+    let mut state = seed.max(1);
+    move |bound| {
+        assert!(bound > 0, "random numbers need a bound above 0, there are none below 0");
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % bound
+    }
+}
+
+pub fn random(seed: u64) -> impl FnMut(usize) -> usize {
+    let mut random = random_u64(seed);
+    move |bound| random(bound as u64) as usize
+}
+
+pub trait Answers {
+    // the answers of the parts, in order, wide enough for every day (4b sums its copies as u128)
+    fn parts(&self) -> Vec<u128>;
+}
+
+pub fn generate_command<A: Answers>(
+    args: &[String],
+    usage: &str,
+    sizes: usize,
+    generate: impl FnOnce(&[String], u64, BufWriter<StdoutLock<'static>>) -> io::Result<A>,
+) -> bool {
+    // Runs `generate <sizes...> [<seed>]`, if that is what the arguments (without the program name) ask for:
+    // the input goes to stdout, the answers to stderr. Returns whether it did.
+    if args.first().map(String::as_str) != Some("generate") { return false; }
+    if args.len() < 1 + sizes {
+        eprintln!("{}", usage);
+        std::process::exit(2);
+    }
+
+    let seed = args.get(1 + sizes).map_or(1, |seed| seed.parse().expect("seed must be a number"));
+    let answers = generate(&args[1..1 + sizes], seed, BufWriter::new(stdout().lock())).unwrap();
+    for (part, answer) in answers.parts().iter().enumerate() {
+        eprintln!("part {}: {}", part + 1, answer);
+    }
+    true
+}
+
+
+#[cfg(test)]
+mod seeds {
+    use crate::{generate_command, random, random_u64, Answers};
+
+    struct NoAnswers;
+
+    impl Answers for NoAnswers {
+        fn parts(&self) -> Vec<u128> { vec![] }
+    }
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let numbers = |seed| { let mut random = random(seed); (0..100).map(|_| random(1000)).collect::<Vec<_>>() };
+        assert_eq!(numbers(42), numbers(42));
+        assert_ne!(numbers(42), numbers(43));
+        assert!(numbers(42).iter().all(|&number| number < 1000));
+    }
+
+    #[test]
+    fn seed_zero() {
+        let mut zero = random_u64(0);
+        let mut one = random_u64(1);
+        assert!((0..10).all(|_| zero(u64::MAX) == one(u64::MAX)));
+        assert_ne!(random_u64(0)(u64::MAX), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "random numbers need a bound above 0")]
+    fn bound_zero() {
+        random(42)(0);
+    }
+
+    #[test]
+    fn other_commands() {
+        let args: Vec<String> = vec!["src/input".to_string()];
+        assert!(!generate_command(&args, "usage", 1, |_, _, _| -> std::io::Result<NoAnswers> { unreachable!() }));
+        assert!(!generate_command(&[], "usage", 1, |_, _, _| -> std::io::Result<NoAnswers> { unreachable!() }));
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::env::{self, args};
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::process::{exit, Command};
+use std::time::{Duration, Instant};
+
+use registry::{parse_registry, Entry};
+
+mod registry;
+
+fn main() {
+    // Usage: verify [<registry>]    (defaults to the answers file next to the days)
+    // Builds every registered solver, runs it on every registered input and compares its answer.
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf();
+    let registry = args().nth(1).map_or_else(|| root.join("answers"), PathBuf::from);
+
+    let entries = parse_registry(&read_to_string(&registry).unwrap()).unwrap_or_else(|error| {
+        eprintln!("{}: {}", registry.display(), error);
+        exit(1);
+    });
+
+    let mut binaries: HashMap<String, Option<PathBuf>> = HashMap::new();
+    let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+
+    for entry in &entries {
+        let solver = entry.solver();
+        let binary = binaries.entry(solver.clone()).or_insert_with(|| build(&root.join(&solver)));
+
+        let outcome = match (binary, input(&root, entry)) {
+            (None, _) => Outcome::Fail("does not build".to_string()),
+            (_, None) => Outcome::Skip,
+            (Some(binary), Some(input)) => run(&root.join(&solver), binary, &input, &entry.answer),
+        };
+
+        let label = format!("{:>2}{} {:<10}", entry.day, entry.part, entry.input);
+        match outcome {
+            Outcome::Pass(time) => { passed += 1; println!("{label} pass  {:>10.3} ms", millis(time)) }
+            Outcome::Fail(why) => { failed += 1; println!("{label} FAIL  {why}") }
+            Outcome::Skip => { skipped += 1; println!("{label} skip  input missing") }
+        }
+    }
+
+    println!("{passed} passed, {failed} failed, {skipped} skipped");
+    if failed > 0 { exit(1); }
+}
+
+enum Outcome {
+    Pass(Duration),
+    Fail(String),
+    Skip,
+}
+
+fn build(dir: &Path) -> Option<PathBuf> {
+    // Release builds, as the inputs are timed.
+    // Every day is a crate of its own, building into its own target directory (unless told otherwise).
+    let status = Command::new(env::var("CARGO").unwrap_or("cargo".to_string()))
+        .args(["build", "--release", "--quiet", "--manifest-path"])
+        .arg(dir.join("Cargo.toml"))
+        .status()
+        .ok()?;
+    if !status.success() { return None; }
+
+    let target = env::var_os("CARGO_TARGET_DIR").map_or_else(|| dir.join("target"), PathBuf::from);
+    Some(target.join("release").join(dir.file_name()?))
+}
+
+fn input(root: &Path, entry: &Entry) -> Option<PathBuf> {
+    // the solver's own src directory first, then the one of the day
+    [entry.solver(), entry.day_crate()].iter()
+        .map(|dir| root.join(dir).join("src").join(&entry.input))
+        .find(|path| path.is_file())
+}
+
+fn run(dir: &Path, binary: &Path, input: &Path, answer: &str) -> Outcome {
+    // The solver runs in its crate, as it would with cargo run, and prints its answer alone on stdout.
+    // The input goes by --input, as some solvers take other arguments first.
+    let start = Instant::now();
+    let output = match Command::new(binary).arg("--input").arg(input).current_dir(dir).output() {
+        Ok(output) => output,
+        Err(error) => return Outcome::Fail(format!("does not run: {error}")),
+    };
+    let time = start.elapsed();
+
+    let got = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    match (output.status.success(), got == answer) {
+        (true, true) => Outcome::Pass(time),
+        (true, false) => Outcome::Fail(format!("expected {answer}, got {got}")),
+        (false, _) => Outcome::Fail(format!("exits with {}: {}", output.status,
+                                            String::from_utf8_lossy(&output.stderr).trim())),
+    }
+}
+
+fn millis(time: Duration) -> f64 {
+    time.as_secs_f64() * 1000.0
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub day: u8,
+    // a or b
+    pub part: char,
+    // file name within the src directory, e.g. "input" or "example2"
+    pub input: String,
+    pub answer: String,
+}
+
+impl Entry {
+    pub fn solver(&self) -> String {
+        // the binary crate solving the part, e.g. aoc01a
+        format!("aoc{:02}{}", self.day, self.part)
+    }
+
+    pub fn day_crate(&self) -> String {
+        format!("aoc{:02}", self.day)
+    }
+}
+
+pub fn parse_registry(s: &str) -> Result<Vec<Entry>, RegistryError> {
+    // # comments start with a hash
+    // 1 a  input    55130    (day, part, input name and expected answer)
+    let mut entries = Vec::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let error = |message: String| RegistryError { line: i + 1, message };
+
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() { continue; }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [day, part, input, answer] = fields[..] else {
+            return Err(error(format!("expected 'day part input answer', got '{line}'")));
+        };
+
+        let day: u8 = day.parse().ok()
+            .filter(|day| (1..=25).contains(day))
+            .ok_or_else(|| error(format!("expected a day from 1 to 25, got '{day}'")))?;
+        let part = match part {
+            "a" | "b" => part.chars().next().unwrap(),
+            _ => return Err(error(format!("expected part a or b, got '{part}'"))),
+        };
+        if input.contains(['/', '\\']) {
+            return Err(error(format!("expected the name of a file in src, got '{input}'")));
+        }
+
+        let entry = Entry { day, part, input: input.to_string(), answer: answer.to_string() };
+        if entries.iter().any(|e: &Entry| (e.day, e.part, &e.input) == (day, part, &entry.input)) {
+            return Err(error(format!("{}{} {} is registered twice", day, part, entry.input)));
+        }
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RegistryError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+
+#[cfg(test)]
+mod parsing {
+    use std::fs::read_to_string;
+
+    use crate::registry::{parse_registry, Entry, RegistryError};
+
+    #[test]
+    fn entries() {
+        let entries = parse_registry("# day part input answer\n\n 1 a  input  55130   # from the comment\n10 b example2 8").unwrap();
+        assert_eq!(entries, vec![
+            Entry { day: 1, part: 'a', input: "input".to_string(), answer: "55130".to_string() },
+            Entry { day: 10, part: 'b', input: "example2".to_string(), answer: "8".to_string() },
+        ]);
+        assert_eq!(entries[0].solver(), "aoc01a");
+        assert_eq!(entries[0].day_crate(), "aoc01");
+        assert_eq!(entries[1].solver(), "aoc10b");
+
+        assert_eq!(parse_registry(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn answers_file() {
+        let entries = parse_registry(&read_to_string("../answers").unwrap()).unwrap();
+        assert!(entries.contains(&Entry { day: 3, part: 'a', input: "input".to_string(), answer: "553079".to_string() }));
+    }
+
+    #[test]
+    fn errors() {
+        let error = |line: usize, message: &str| Err(RegistryError { line, message: message.to_string() });
+
+        assert_eq!(parse_registry("1 a input"), error(1, "expected 'day part input answer', got '1 a input'"));
+        assert_eq!(parse_registry("\n26 a input 1"), error(2, "expected a day from 1 to 25, got '26'"));
+        assert_eq!(parse_registry("1 c input 1"), error(1, "expected part a or b, got 'c'"));
+        assert_eq!(parse_registry("1 a ../input 1"), error(1, "expected the name of a file in src, got '../input'"));
+        assert_eq!(parse_registry("1 a input 1\n1 a input 2"), error(2, "1a input is registered twice"));
+    }
+}